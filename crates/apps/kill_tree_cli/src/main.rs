@@ -2,9 +2,12 @@ use std::io;
 
 use clap::{
     builder::{styling::AnsiColor, Styles},
-    command, value_parser, ArgAction, Parser,
+    value_parser, ArgAction, ArgGroup, Parser,
+};
+use kill_tree::{
+    blocking::{kill_tree_with_config, kill_trees_by_selector},
+    Config, ErrorPolicy, Output, ProcessSelector, Signal,
 };
-use kill_tree::{blocking::kill_tree_with_config, Config};
 use tracing::{
     subscriber::{self, SetGlobalDefaultError},
     Level,
@@ -25,14 +28,47 @@ fn get_styles() -> Styles {
 #[command(arg_required_else_help = true)]
 #[command(styles = get_styles())]
 #[command(author, version, about, long_about=None)]
+#[command(group(ArgGroup::new("target").required(true).multiple(true).args(["process_id", "name", "cmdline_regex"])))]
 struct Cli {
     #[arg(help = "Process ID to kill with all children.")]
     #[arg(value_parser = value_parser!(u32))]
-    process_id: u32,
+    #[arg(conflicts_with_all = ["name", "cmdline_regex"])]
+    process_id: Option<u32>,
 
-    #[arg(help = "Signal to send to the processes.")]
+    #[arg(help = "Signal to send to the processes. e.g. SIGTERM, TERM, 15")]
     #[arg(default_value = "SIGTERM")]
-    signal: String,
+    signal: Signal,
+
+    #[arg(short = 's', long = "signal", id = "signal_option")]
+    #[arg(
+        help = "Signal to send to the processes, also with --name or --cmdline-regex. Overrides the positional signal."
+    )]
+    signal_option: Option<Signal>,
+
+    #[arg(long)]
+    #[arg(
+        help = "Kill the trees of the processes with this name, instead of a process ID. Combined with --cmdline-regex, both must match."
+    )]
+    name: Option<String>,
+
+    #[arg(long)]
+    #[arg(
+        help = "Kill the trees of the processes whose full command line matches this regular expression, instead of a process ID. Combined with --name, both must match."
+    )]
+    #[arg(value_parser = value_parser!(regex::Regex))]
+    cmdline_regex: Option<regex::Regex>,
+
+    #[arg(long)]
+    #[arg(help = "Mount point of procfs to query the processes from on Linux. e.g. /host/proc")]
+    #[arg(default_value = "/proc")]
+    proc_root: std::path::PathBuf,
+
+    #[arg(long)]
+    #[arg(
+        help = "Keep killing the other processes if a process cannot be signalled, and report it."
+    )]
+    #[arg(action = ArgAction::SetTrue)]
+    continue_on_error: bool,
 
     #[arg(short, long)]
     #[arg(help = "No logs are output.")]
@@ -50,24 +86,117 @@ fn init_log(level: Level) -> Result<(), SetGlobalDefaultError> {
     subscriber::set_global_default(FmtSubscriber::builder().with_max_level(level).finish())
 }
 
+fn print_outputs(outputs: &[Output]) {
+    for (index, output) in outputs.iter().enumerate() {
+        match output {
+            Output::Killed {
+                process_id,
+                parent_process_id,
+                name,
+                ..
+            } => {
+                println!(
+                    "[{index}] Killed process. process id: {process_id}, parent process id: {parent_process_id}, name: {name}"
+                );
+            }
+            Output::MaybeAlreadyTerminated {
+                process_id, source, ..
+            } => {
+                println!(
+                    "[{index}] Maybe already terminated process. process id: {process_id}, source: {source}"
+                );
+            }
+            Output::PidReused {
+                process_id,
+                parent_process_id,
+                name,
+                ..
+            } => {
+                println!(
+                    "[{index}] Skipped reused process id. process id: {process_id}, parent process id: {parent_process_id}, name: {name}"
+                );
+            }
+            Output::Failed {
+                process_id, source, ..
+            } => {
+                println!(
+                    "[{index}] Failed to kill process. process id: {process_id}, source: {source}"
+                );
+            }
+        }
+    }
+}
+
+fn get_selector(cli: &Cli) -> ProcessSelector {
+    let mut selector = ProcessSelector::new();
+    if let Some(name) = &cli.name {
+        selector = selector.name(name.clone());
+    }
+    if let Some(cmdline_regex) = &cli.cmdline_regex {
+        let cmdline_regex = cmdline_regex.clone();
+        selector = selector.cmdline(move |x| cmdline_regex.is_match(x));
+    }
+    selector
+}
+
 fn main() -> kill_tree::Result<()> {
     let cli = Cli::parse();
     let do_print = !cli.quiet;
     if do_print {
         init_log(cli.log_level).map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    }
+    let config = Config {
+        signal: cli.signal_option.unwrap_or(cli.signal),
+        proc_root: cli.proc_root.clone(),
+        error_policy: if cli.continue_on_error {
+            ErrorPolicy::ContinueAndCollect
+        } else {
+            ErrorPolicy::FailFast
+        },
+        ..Default::default()
+    };
+
+    let Some(process_id) = cli.process_id else {
+        if do_print {
+            println!(
+                "Killing all of selected processes and their children recursively. signal: {}",
+                config.signal
+            );
+        }
+        let tree_outputs = match kill_trees_by_selector(&get_selector(&cli), &config) {
+            Ok(x) => x,
+            Err(e) => {
+                if do_print {
+                    println!("Failed to kill processes. error: {e}");
+                }
+                return Err(e);
+            }
+        };
+        if do_print {
+            println!(
+                "Killing is done. Number of selected processes: {}",
+                tree_outputs.len()
+            );
+            for tree_output in &tree_outputs {
+                println!(
+                    "Selected process id: {}, number of killed processes: {}",
+                    tree_output.process_id,
+                    tree_output.outputs.len()
+                );
+                print_outputs(&tree_output.outputs);
+            }
+        }
+        return Ok(());
+    };
+
+    if do_print {
         println!(
-            "Killing all of target process and its children recursively. process id: {}, signal: {}",
-            cli.process_id, cli.signal
+            "Killing all of target process and its children recursively. process id: {process_id}, signal: {}",
+            config.signal
         );
     }
 
-    let outputs = match kill_tree_with_config(
-        cli.process_id,
-        &Config {
-            signal: cli.signal,
-            ..Default::default()
-        },
-    ) {
+    let outputs = match kill_tree_with_config(process_id, &config) {
         Ok(x) => x,
         Err(e) => {
             if do_print {
@@ -82,24 +211,7 @@ fn main() -> kill_tree::Result<()> {
             "Killing is done. Number of killed processes: {}",
             outputs.len()
         );
-        for (index, output) in outputs.iter().enumerate() {
-            match output {
-                kill_tree::Output::Killed {
-                    process_id,
-                    parent_process_id,
-                    name,
-                } => {
-                    println!(
-                        "[{index}] Killed process. process id: {process_id}, parent process id: {parent_process_id}, name: {name}"
-                    );
-                }
-                kill_tree::Output::MaybeAlreadyTerminated { process_id, source } => {
-                    println!(
-                        "[{index}] Maybe already terminated process. process id: {process_id}, source: {source}"
-                    );
-                }
-            }
-        }
+        print_outputs(&outputs);
     }
     Ok(())
 }
@@ -10,16 +10,36 @@ fn main() -> Result<()> {
                 process_id,
                 parent_process_id,
                 name,
+                ..
             } => {
                 println!(
                     "[{index}] Killed process. process id: {process_id}, parent process id: {parent_process_id}, name: {name}"
                 );
             }
-            Output::MaybeAlreadyTerminated { process_id, source } => {
+            Output::MaybeAlreadyTerminated {
+                process_id, source, ..
+            } => {
                 println!(
                     "[{index}] Maybe already terminated process. process id: {process_id}, source: {source}"
                 );
             }
+            Output::PidReused {
+                process_id,
+                parent_process_id,
+                name,
+                ..
+            } => {
+                println!(
+                    "[{index}] Skipped reused process id. process id: {process_id}, parent process id: {parent_process_id}, name: {name}"
+                );
+            }
+            Output::Failed {
+                process_id, source, ..
+            } => {
+                println!(
+                    "[{index}] Failed to kill process. process id: {process_id}, source: {source}"
+                );
+            }
         }
     }
     Ok(())
@@ -1,10 +1,10 @@
 // crates/examples/readme/src/kill_tree_sigkill.rs
-use kill_tree::{blocking::kill_tree_with_config, Config, Result};
+use kill_tree::{blocking::kill_tree_with_config, Config, Result, Signal};
 
 fn main() -> Result<()> {
     let process_id = 777;
     let config = Config {
-        signal: "SIGKILL".to_string(),
+        signal: Signal::SIGKILL,
         ..Default::default()
     };
     let outputs = kill_tree_with_config(process_id, &config)?;
@@ -18,7 +18,7 @@ fn kill_tree_with_sigkill(b: &mut Bencher) {
     b.iter(|| {
         let target_process_id = get_available_max_process_id();
         let config = Config {
-            signal: String::from("SIGKILL"),
+            signal: kill_tree::Signal::SIGKILL,
             ..Default::default()
         };
         kill_tree::blocking::kill_tree_with_config(target_process_id, &config).unwrap();
@@ -36,3 +36,303 @@ fn kill_tree_exclude_target(b: &mut Bencher) {
         kill_tree::blocking::kill_tree_with_config(target_process_id, &config).unwrap();
     });
 }
+
+/// Scans every process of `/proc` reading only stat, with one reused buffer.
+#[cfg(target_os = "linux")]
+#[bench]
+fn get_process_infos_stat(b: &mut Bencher) {
+    b.iter(|| test::black_box(kill_tree::blocking::get_process_infos().unwrap()));
+}
+
+/// Scans every process of `/proc` as before stat was read directly, on the same input as `get_process_infos_stat`.
+#[cfg(target_os = "linux")]
+#[bench]
+fn get_process_infos_status_and_stat(b: &mut Bencher) {
+    b.iter(|| test::black_box(status_and_stat::get_process_infos().unwrap()));
+}
+
+/// The scan before stat was read directly, as it was in the crate.  
+/// It checks each entry, and reads status and stat into new strings for each process.  
+#[cfg(target_os = "linux")]
+mod status_and_stat {
+    use kill_tree::{Error, ProcessId, ProcessInfo, ProcessInfos, Result};
+
+    fn parse_status(
+        process_id: ProcessId,
+        status_path: String,
+        status: &str,
+    ) -> Result<ProcessInfo> {
+        let mut parent_process_id = None;
+        let mut name = None;
+        let mut user_id = None;
+        let mut group_id = None;
+        for line in status.lines() {
+            if parent_process_id.is_some()
+                && name.is_some()
+                && user_id.is_some()
+                && group_id.is_some()
+            {
+                break;
+            }
+
+            if line.starts_with("PPid:") {
+                let Some(parent_process_id_str) = line.split_whitespace().nth(1) else {
+                    return Err(Error::InvalidProcEntry {
+                        process_id,
+                        path: status_path,
+                        reason: "PPid line is invalid".into(),
+                        source: None,
+                    });
+                };
+
+                let parent_process_id_value = match parent_process_id_str.parse::<u32>() {
+                    Ok(x) => x,
+                    Err(e) => {
+                        return Err(Error::InvalidProcEntry {
+                            process_id,
+                            path: status_path,
+                            reason: "Failed to parse parent process id".into(),
+                            source: Some(e),
+                        });
+                    }
+                };
+
+                parent_process_id = Some(parent_process_id_value);
+            }
+
+            if let Some(name_str) = line.strip_prefix("Name:") {
+                // the name can contain whitespaces, e.g. "Web Content"
+                let name_value = name_str.strip_prefix('\t').unwrap_or(name_str.trim_start());
+                if name_value.is_empty() {
+                    return Err(Error::InvalidProcEntry {
+                        process_id,
+                        path: status_path,
+                        reason: "Name line is invalid".into(),
+                        source: None,
+                    });
+                }
+
+                name = Some(name_value.to_string());
+            }
+
+            if line.starts_with("Uid:") {
+                user_id = Some(parse_status_id(process_id, &status_path, line, "Uid")?);
+            }
+
+            if line.starts_with("Gid:") {
+                group_id = Some(parse_status_id(process_id, &status_path, line, "Gid")?);
+            }
+        }
+
+        let Some(parent_process_id) = parent_process_id else {
+            return Err(Error::InvalidProcEntry {
+                process_id,
+                path: status_path,
+                reason: "Parent process id is None".into(),
+                source: None,
+            });
+        };
+
+        let Some(name) = name else {
+            return Err(Error::InvalidProcEntry {
+                process_id,
+                path: status_path,
+                reason: "Name is None".into(),
+                source: None,
+            });
+        };
+
+        let mut process_info = ProcessInfo::new(process_id, parent_process_id, name);
+        process_info.user_id = user_id;
+        process_info.group_id = group_id;
+        Ok(process_info)
+    }
+
+    fn parse_status_id(
+        process_id: ProcessId,
+        status_path: &str,
+        line: &str,
+        key: &str,
+    ) -> Result<u32> {
+        let Some(id_str) = line.split_whitespace().nth(1) else {
+            return Err(Error::InvalidProcEntry {
+                process_id,
+                path: status_path.to_string(),
+                reason: format!("{key} line is invalid"),
+                source: None,
+            });
+        };
+
+        match id_str.parse::<u32>() {
+            Ok(x) => Ok(x),
+            Err(e) => Err(Error::InvalidProcEntry {
+                process_id,
+                path: status_path.to_string(),
+                reason: format!("Failed to parse {key}"),
+                source: Some(e),
+            }),
+        }
+    }
+
+    struct Stat {
+        state: String,
+        process_group_id: ProcessId,
+        session_id: ProcessId,
+        start_time: u64,
+    }
+
+    fn parse_stat_field<T>(
+        process_id: ProcessId,
+        stat_path: &str,
+        fields: &[&str],
+        number: usize,
+        field_name: &str,
+    ) -> Result<T>
+    where
+        T: std::str::FromStr<Err = std::num::ParseIntError>,
+    {
+        let Some(field) = fields.get(number - 3) else {
+            return Err(Error::InvalidProcEntry {
+                process_id,
+                path: stat_path.to_string(),
+                reason: format!("{field_name} is None"),
+                source: None,
+            });
+        };
+        match field.parse::<T>() {
+            Ok(x) => Ok(x),
+            Err(e) => Err(Error::InvalidProcEntry {
+                process_id,
+                path: stat_path.to_string(),
+                reason: format!("Failed to parse {field_name}"),
+                source: Some(e),
+            }),
+        }
+    }
+
+    fn parse_stat(process_id: ProcessId, stat_path: String, stat: &str) -> Result<Stat> {
+        let Some(fields) = stat.rfind(')').map(|index| &stat[index + 1..]) else {
+            return Err(Error::InvalidProcEntry {
+                process_id,
+                path: stat_path,
+                reason: "Name is not closed".into(),
+                source: None,
+            });
+        };
+        let fields = fields.split_whitespace().collect::<Vec<_>>();
+        let Some(state) = fields.first() else {
+            return Err(Error::InvalidProcEntry {
+                process_id,
+                path: stat_path,
+                reason: "State is None".into(),
+                source: None,
+            });
+        };
+        // the parent process id of the 4th field was parsed too
+        let _: ProcessId =
+            parse_stat_field(process_id, &stat_path, &fields, 4, "parent process id")?;
+        Ok(Stat {
+            state: (*state).to_string(),
+            process_group_id: parse_stat_field(
+                process_id,
+                &stat_path,
+                &fields,
+                5,
+                "process group id",
+            )?,
+            session_id: parse_stat_field(process_id, &stat_path, &fields, 6, "session id")?,
+            start_time: parse_stat_field(process_id, &stat_path, &fields, 22, "start time")?,
+        })
+    }
+
+    fn parse_proc_entry(
+        process_id: ProcessId,
+        path: &std::path::Path,
+    ) -> Result<std::path::PathBuf> {
+        if !path.is_dir() {
+            return Err(Error::InvalidProcEntry {
+                process_id,
+                path: path.display().to_string(),
+                reason: "Proc entry is not a directory".into(),
+                source: None,
+            });
+        }
+
+        let Some(file_name) = path.file_name().and_then(|s| s.to_str()) else {
+            return Err(Error::InvalidProcEntry {
+                process_id,
+                path: path.display().to_string(),
+                reason: "Failed to get file name".into(),
+                source: None,
+            });
+        };
+
+        let process_id = match file_name.parse::<u32>() {
+            Ok(x) => x,
+            Err(e) => {
+                return Err(Error::InvalidProcEntry {
+                    process_id,
+                    path: path.display().to_string(),
+                    reason: "Failed to parse process id".into(),
+                    source: Some(e),
+                });
+            }
+        };
+
+        let status_path = path.join("status");
+        if !status_path.exists() {
+            return Err(Error::InvalidProcEntry {
+                process_id,
+                path: status_path.display().to_string(),
+                reason: "Status path does not exist".into(),
+                source: None,
+            });
+        }
+
+        if !status_path.is_file() {
+            return Err(Error::InvalidProcEntry {
+                process_id,
+                path: status_path.display().to_string(),
+                reason: "Status path is not a file".into(),
+                source: None,
+            });
+        }
+
+        Ok(status_path)
+    }
+
+    fn get_process_info(process_id: ProcessId, path: std::path::PathBuf) -> Result<ProcessInfo> {
+        let status_path = parse_proc_entry(process_id, &path)?;
+        let status = std::fs::read_to_string(&status_path)?;
+        let mut process_info =
+            parse_status(process_id, status_path.display().to_string(), &status)?;
+        let stat_path = path.join("stat");
+        let stat = std::fs::read_to_string(&stat_path)?;
+        let stat = parse_stat(process_id, stat_path.display().to_string(), &stat)?;
+        process_info.process_group_id = Some(stat.process_group_id);
+        process_info.session_id = Some(stat.session_id);
+        process_info.start_time = Some(stat.start_time);
+        process_info.state = stat.state.chars().next();
+        Ok(process_info)
+    }
+
+    pub(crate) fn get_process_infos() -> Result<ProcessInfos> {
+        let read_dir = std::fs::read_dir("/proc")?;
+        let mut process_infos = ProcessInfos::new();
+        for entry_result in read_dir {
+            let entry = entry_result?;
+            let file_name = entry.file_name();
+            let Some(file_name) = file_name.to_str() else {
+                continue;
+            };
+            let Ok(process_id) = file_name.parse::<u32>() else {
+                continue;
+            };
+            let Ok(process_info) = get_process_info(process_id, entry.path()) else {
+                continue;
+            };
+            process_infos.push(process_info);
+        }
+        Ok(process_infos)
+    }
+}
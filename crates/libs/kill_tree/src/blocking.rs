@@ -1,4 +1,5 @@
-use crate::core::{blocking::ProcessInfosProvidable, Config, Outputs, ProcessId, Result};
+pub use crate::core::blocking::ProcessInfosProvidable;
+use crate::core::{Config, KillPlan, Outputs, ProcessId, ProcessSelector, Result, TreeOutputs};
 
 #[cfg(target_os = "linux")]
 use crate::linux as imp;
@@ -15,6 +16,8 @@ use crate::windows as imp;
 ///
 /// ## Linux, Macos
 /// Internally, `kill` from `libc` is used.  
+/// On Linux 5.3 or later, `pidfd_send_signal` is used instead, so that a reused process ID is never signalled.  
+/// Before each signal, the start time of the process is checked, and `Output::PidReused` is returned if the process ID has been reused.  
 /// Default signal is `SIGTERM`.  
 /// If you want to send a different `signal` instead of `SIGTERM`, use `kill_tree_with_config`.  
 ///
@@ -61,19 +64,49 @@ pub fn kill_tree(process_id: ProcessId) -> Result<Outputs> {
 ///
 /// ## Linux, Macos
 /// Internally, `kill` from `libc` is used.  
+/// On Linux 5.3 or later, `pidfd_send_signal` is used instead, so that a reused process ID is never signalled.  
+/// Before each signal, the start time of the process is checked, and `Output::PidReused` is returned if the process ID has been reused.  
 ///
 /// # Examples
 ///
 /// Kill processes using the `SIGKILL` signal.  
 /// ```
 /// use kill_tree::{
-///     blocking::kill_tree_with_config, get_available_max_process_id, Config, Result,
+///     blocking::kill_tree_with_config, get_available_max_process_id, Config, Result, Signal,
+/// };
+///
+/// fn main() -> Result<()> {
+///     let target_process_id = get_available_max_process_id(); // Replace with your target process ID.
+///     let config = Config {
+///         signal: Signal::SIGKILL,
+///         ..Default::default()
+///     };
+///     let _ = kill_tree_with_config(target_process_id, &config)?;
+///     Ok(())
+/// }
+/// ```
+///
+/// Sends `SIGTERM`, then sends `SIGKILL` to the processes still alive after 5 seconds.  
+/// ```
+/// use kill_tree::{
+///     blocking::kill_tree_with_config, get_available_max_process_id, Config, EscalationStep,
+///     Result, Signal,
 /// };
+/// use std::time::Duration;
 ///
 /// fn main() -> Result<()> {
 ///     let target_process_id = get_available_max_process_id(); // Replace with your target process ID.
 ///     let config = Config {
-///         signal: String::from("SIGKILL"),
+///         escalation: vec![
+///             EscalationStep {
+///                 signal: Signal::SIGTERM,
+///                 timeout: Duration::from_secs(5),
+///             },
+///             EscalationStep {
+///                 signal: Signal::SIGKILL,
+///                 timeout: Duration::from_secs(1),
+///             },
+///         ],
 ///         ..Default::default()
 ///     };
 ///     let _ = kill_tree_with_config(target_process_id, &config)?;
@@ -102,6 +135,7 @@ pub fn kill_tree(process_id: ProcessId) -> Result<Outputs> {
 ///
 /// ## `InvalidProcessId`
 /// Returns the process ID of the kernel or system, or if greater than the available maximum process ID.  
+/// Also returned on Linux if the target process in `Config::proc_root` is not visible from the current pid namespace.  
 ///
 /// ## `InvalidCast`
 /// Returned internally when an invalid type conversion occurs during a system API call.  
@@ -120,9 +154,327 @@ pub fn kill_tree(process_id: ProcessId) -> Result<Outputs> {
 /// Returned when the `libc` API used internally fails.  
 pub fn kill_tree_with_config(process_id: ProcessId, config: &Config) -> Result<Outputs> {
     imp::validate_process_id(process_id)?;
+    let process_infos_provider =
+        imp::blocking::new_tree_process_infos_provider(&[process_id], config);
+    // walked from the process id as listed in the procfs, but signalled with the one of the current pid namespace
+    let process_id = imp::get_caller_process_id(process_id, config)?;
+    crate::common::blocking::kill_tree_internal(process_id, config, &process_infos_provider)
+}
+
+/// Kills the target process and all of its children recursively using the given `Config`,
+/// with the process tree built from the process infos of the given provider instead of the operating system.  
+/// The signals are sent to the real processes, so use `plan_kill_tree_with_provider` to see what would be killed.  
+///
+/// # Examples
+/// ```
+/// use kill_tree::{
+///     blocking::kill_tree_with_provider, get_available_max_process_id, Config, ProcessInfo, Result,
+///     StaticProcessTable,
+/// };
+///
+/// fn main() -> Result<()> {
+///     let target_process_id = get_available_max_process_id(); // Replace with your target process ID.
+///     let process_table =
+///         StaticProcessTable::default().process(ProcessInfo::new(target_process_id, 1, "server"));
+///     let outputs =
+///         kill_tree_with_provider(target_process_id, &Config::default(), &process_table)?;
+///     println!("outputs: {outputs:?}");
+///     Ok(())
+/// }
+/// ```
+///
+/// # Errors
+///
+/// ## `InvalidProcessId`
+/// Returns the process ID of the kernel or system, or if greater than the available maximum process ID.  
+///
+/// ## `InvalidCast`
+/// Returned internally when an invalid type conversion occurs during a system API call.  
+/// This is an error that should not occur under normal circumstances.  
+///
+/// ## `Windows`
+/// Returned when the `Win32` API used internally fails.  
+///
+/// ## `Unix`
+/// Returned when the `libc` API used internally fails.  
+///
+/// Any error returned by the provider is returned as is.  
+pub fn kill_tree_with_provider(
+    process_id: ProcessId,
+    config: &Config,
+    process_infos_provider: &impl ProcessInfosProvidable,
+) -> Result<Outputs> {
+    imp::validate_process_id(process_id)?;
+    crate::common::blocking::kill_tree_internal(process_id, config, process_infos_provider)
+}
+
+/// Kills several target processes and all of their children recursively using the given `Config`.  
+/// The process infos are queried once for all target processes, and a process in overlapping trees is killed only once.  
+/// The processes of each tree are killed in the order of `Config::kill_order`, children first by default.  
+/// Returns the outputs of each target process in the given order, without duplicated target processes.  
+/// A process in the trees of several target processes is reported in the tree of the nearest one.  
+/// If `include_target` is `false`, no target process is killed, even if it is in the tree of another target process.  
+///
+/// # Examples
+/// ```
+/// use kill_tree::{get_available_max_process_id, blocking::kill_trees, Config, Result};
+///
+/// fn main() -> Result<()> {
+///     let target_process_ids = [get_available_max_process_id()]; // Replace with your target process IDs.
+///     let tree_outputs = kill_trees(&target_process_ids, &Config::default())?;
+///     for tree_output in &tree_outputs {
+///         println!("{}: {:?}", tree_output.process_id, tree_output.outputs);
+///     }
+///     Ok(())
+/// }
+/// ```
+///
+/// # Errors
+///
+/// ## `InvalidProcessId`
+/// Returns the process ID of the kernel or system, or if greater than the available maximum process ID.  
+/// Also returned on Linux if the target process in `Config::proc_root` is not visible from the current pid namespace.  
+/// No process is killed if any target process ID is invalid.  
+///
+/// ## `InvalidCast`
+/// Returned internally when an invalid type conversion occurs during a system API call.  
+/// This is an error that should not occur under normal circumstances.  
+///
+/// ## `InvalidProcEntry`
+/// Returned when inquiry, or parsing within the Linux `/proc/` path fails.  
+///
+/// ## `Io`
+/// Returned when access within the Linux `/proc/` path fails.  
+///
+/// ## `Windows`
+/// Returned when the `Win32` API used internally fails.  
+///
+/// ## `Unix`
+/// Returned when the `libc` API used internally fails.  
+pub fn kill_trees(process_ids: &[ProcessId], config: &Config) -> Result<Vec<TreeOutputs>> {
+    for &process_id in process_ids {
+        imp::validate_process_id(process_id)?;
+    }
+    let process_infos_provider =
+        imp::blocking::new_tree_process_infos_provider(process_ids, config);
+    let process_ids = process_ids
+        .iter()
+        .map(|&x| imp::get_caller_process_id(x, config))
+        .collect::<Result<Vec<_>>>()?;
+    crate::common::blocking::kill_trees_internal(&process_ids, config, &process_infos_provider)
+}
+
+/// Returns the process IDs matched by the selector, like `pgrep`, without sending any signal.  
+/// A matched process is not returned if any of its ancestors is also matched, because it is in the tree of that ancestor.  
+/// The current process and its ancestors are never matched, since their trees contain the current process.  
+///
+/// # Examples
+/// ```
+/// use kill_tree::{blocking::select_process_ids, ProcessSelector, Result};
+///
+/// fn main() -> Result<()> {
+///     let selector = ProcessSelector::new().name("my-server"); // Replace with your target process name.
+///     let process_ids = select_process_ids(&selector)?;
+///     println!("{process_ids:?}");
+///     Ok(())
+/// }
+/// ```
+///
+/// # Errors
+///
+/// ## `InvalidCast`
+/// Returned internally when an invalid type conversion occurs during a system API call.  
+/// This is an error that should not occur under normal circumstances.  
+///
+/// ## `InvalidProcEntry`
+/// Returned when inquiry, or parsing within the Linux `/proc/` path fails.  
+///
+/// ## `Io`
+/// Returned when access within the Linux `/proc/` path fails.  
+///
+/// ## `Windows`
+/// Returned when the `Win32` API used internally fails.  
+///
+/// ## `Unix`
+/// Returned when the `libc` API used internally fails.  
+pub fn select_process_ids(selector: &ProcessSelector) -> Result<Vec<ProcessId>> {
     let process_infos_provider = imp::blocking::ProcessInfosProvider {};
+    crate::common::blocking::select_process_ids_internal(selector, &process_infos_provider)
+}
+
+/// Kills the trees of the processes matched by the selector, like a tree-aware `pkill`.  
+/// Every matched process is a target process, and the trees are killed together as `kill_trees` does.  
+/// A matched process in the tree of another matched process is not a target process itself.  
+/// The current process and its ancestors are never matched, so the killed trees never contain the current process.  
+/// Returns an empty list if no process is matched.  
+///
+/// # Examples
+/// ```
+/// use kill_tree::{blocking::kill_trees_by_selector, Config, ProcessSelector, Result};
+///
+/// fn main() -> Result<()> {
+///     let selector = ProcessSelector::new().cmdline(|x| x.contains("--port 8080")); // Replace with your target command line.
+///     let tree_outputs = kill_trees_by_selector(&selector, &Config::default())?;
+///     for tree_output in &tree_outputs {
+///         println!("{}: {:?}", tree_output.process_id, tree_output.outputs);
+///     }
+///     Ok(())
+/// }
+/// ```
+///
+/// # Errors
+///
+/// ## `InvalidCast`
+/// Returned internally when an invalid type conversion occurs during a system API call.  
+/// This is an error that should not occur under normal circumstances.  
+///
+/// ## `InvalidProcEntry`
+/// Returned when inquiry, or parsing within the Linux `/proc/` path fails.  
+///
+/// ## `Io`
+/// Returned when access within the Linux `/proc/` path fails.  
+///
+/// ## `Windows`
+/// Returned when the `Win32` API used internally fails.  
+///
+/// ## `Unix`
+/// Returned when the `libc` API used internally fails.  
+pub fn kill_trees_by_selector(
+    selector: &ProcessSelector,
+    config: &Config,
+) -> Result<Vec<TreeOutputs>> {
+    let process_infos_provider = imp::blocking::new_process_infos_provider(config);
+    crate::common::blocking::kill_selected_trees_internal(selector, config, &process_infos_provider)
+}
+
+/// Returns the processes that `kill_tree_with_config` would kill, without sending any signal.  
+/// The processes are ordered as they would be killed, as ordered by `Config::kill_order`.  
+/// Each entry has the process id, parent process id, name, depth from the target and the reason it is included.  
+///
+/// # Examples
+/// ```
+/// use kill_tree::{blocking::plan_kill_tree, get_available_max_process_id, Config, Result};
+///
+/// fn main() -> Result<()> {
+///     let target_process_id = get_available_max_process_id(); // Replace with your target process ID.
+///     let kill_plan = plan_kill_tree(target_process_id, &Config::default())?;
+///     for entry in &kill_plan {
+///         println!("{entry:?}");
+///     }
+///     Ok(())
+/// }
+/// ```
+///
+/// # Errors
+///
+/// ## `InvalidProcessId`
+/// Returns the process ID of the kernel or system, or if greater than the available maximum process ID.  
+/// Also returned on Linux if the target process in `Config::proc_root` is not visible from the current pid namespace.  
+///
+/// ## `InvalidCast`
+/// Returned internally when an invalid type conversion occurs during a system API call.  
+/// This is an error that should not occur under normal circumstances.  
+///
+/// ## `InvalidProcEntry`
+/// Returned when inquiry, or parsing within the Linux `/proc/` path fails.  
+///
+/// ## `Io`
+/// Returned when access within the Linux `/proc/` path fails.  
+///
+/// ## `Windows`
+/// Returned when the `Win32` API used internally fails.  
+pub fn plan_kill_tree(process_id: ProcessId, config: &Config) -> Result<KillPlan> {
+    imp::validate_process_id(process_id)?;
+    let process_infos_provider =
+        imp::blocking::new_tree_process_infos_provider(&[process_id], config);
+    // walked from the process id as listed in the procfs, but signalled with the one of the current pid namespace
+    let process_id = imp::get_caller_process_id(process_id, config)?;
     let process_infos = process_infos_provider.get_process_infos()?;
-    crate::common::kill_tree_internal(process_id, config, process_infos)
+    Ok(crate::common::plan_kill_tree_internal(
+        process_id,
+        config,
+        process_infos,
+    ))
+}
+
+/// Returns the processes that `kill_tree_with_provider` would kill, without sending any signal.  
+/// The process tree is built from the process infos of the given provider, so no real process is needed.  
+///
+/// # Examples
+/// ```
+/// use kill_tree::{
+///     blocking::plan_kill_tree_with_provider, Config, ProcessInfo, Result, StaticProcessTable,
+/// };
+///
+/// fn main() -> Result<()> {
+///     let process_table = StaticProcessTable::default()
+///         .process(ProcessInfo::new(777, 1, "server"))
+///         .process(ProcessInfo::new(778, 777, "worker"));
+///     let kill_plan = plan_kill_tree_with_provider(777, &Config::default(), &process_table)?;
+///     assert_eq!(kill_plan.len(), 2);
+///     assert_eq!(kill_plan[0].process_id, 778);
+///     Ok(())
+/// }
+/// ```
+///
+/// # Errors
+///
+/// ## `InvalidProcessId`
+/// Returns the process ID of the kernel or system, or if greater than the available maximum process ID.  
+///
+/// Any error returned by the provider is returned as is.  
+pub fn plan_kill_tree_with_provider(
+    process_id: ProcessId,
+    config: &Config,
+    process_infos_provider: &impl ProcessInfosProvidable,
+) -> Result<KillPlan> {
+    imp::validate_process_id(process_id)?;
+    let process_infos = process_infos_provider.get_process_infos()?;
+    Ok(crate::common::plan_kill_tree_internal(
+        process_id,
+        config,
+        process_infos,
+    ))
+}
+
+/// Kills all processes in the cgroup v2 and its descendant cgroups at once, by writing `1` to `cgroup.kill`.  
+/// `path` is the directory of the cgroup, e.g. `/sys/fs/cgroup/system.slice/example.service`.  
+/// The processes are killed with `SIGKILL`. `cgroup.kill` is supported since Linux 5.14.  
+/// Returns the processes listed in `cgroup.procs` before killing.  
+///
+/// # Examples
+/// ```no_run
+/// use kill_tree::{blocking::kill_cgroup, Result};
+///
+/// fn main() -> Result<()> {
+///     let outputs = kill_cgroup("/sys/fs/cgroup/system.slice/example.service")?;
+///     println!("outputs: {outputs:?}");
+///     Ok(())
+/// }
+/// ```
+///
+/// # Errors
+///
+/// ## `InvalidProcEntry`
+/// Returned when inquiry, or parsing within the Linux `/proc/` path fails.  
+///
+/// ## `Io`
+/// Returned when access within the Linux `/proc/` path or the cgroup fails.  
+#[cfg(target_os = "linux")]
+pub fn kill_cgroup(path: impl AsRef<std::path::Path>) -> Result<Outputs> {
+    let path = path.as_ref();
+    let process_ids = crate::linux::get_cgroup_process_ids(path)?;
+    let process_infos_provider = imp::blocking::ProcessInfosProvider {};
+    let process_infos = process_infos_provider.get_process_infos()?;
+    crate::linux::kill_cgroup(path, process_ids, process_infos)
+}
+
+/// Scans every process of `/proc`, as the kill functions do when the tree cannot be walked down.  
+/// Only for the benchmarks, so it is not documented.  
+#[doc(hidden)]
+#[cfg(target_os = "linux")]
+pub fn get_process_infos() -> Result<crate::core::ProcessInfos> {
+    imp::blocking::get_process_infos()
 }
 
 #[cfg(test)]
@@ -138,10 +490,14 @@ mod tests {
         assert_eq!(result.len(), 1);
         let output = &result[0];
         match output {
-            crate::Output::Killed { .. } => {
+            crate::Output::Killed { .. }
+            | crate::Output::PidReused { .. }
+            | crate::Output::Failed { .. } => {
                 panic!("This should not happen");
             }
-            crate::Output::MaybeAlreadyTerminated { process_id, source } => {
+            crate::Output::MaybeAlreadyTerminated {
+                process_id, source, ..
+            } => {
                 assert_eq!(*process_id, target_process_id);
                 assert_eq!(
                     source.to_string(),
@@ -159,10 +515,14 @@ mod tests {
         assert_eq!(result.len(), 1);
         let output = &result[0];
         match output {
-            crate::Output::Killed { .. } => {
+            crate::Output::Killed { .. }
+            | crate::Output::PidReused { .. }
+            | crate::Output::Failed { .. } => {
                 panic!("This should not happen");
             }
-            crate::Output::MaybeAlreadyTerminated { process_id, source } => {
+            crate::Output::MaybeAlreadyTerminated {
+                process_id, source, ..
+            } => {
                 assert_eq!(*process_id, target_process_id);
                 assert_eq!(source.to_string(), "Unix error: ESRCH: No such process");
             }
@@ -174,17 +534,21 @@ mod tests {
     fn kill_tree_with_config_sigkill_available_max_process_id_windows() {
         let target_process_id = get_available_max_process_id();
         let config = Config {
-            signal: String::from("SIGKILL"),
+            signal: crate::Signal::SIGKILL,
             ..Default::default()
         };
         let result = kill_tree_with_config(target_process_id, &config).expect("Failed to kill");
         assert_eq!(result.len(), 1);
         let output = &result[0];
         match output {
-            crate::Output::Killed { .. } => {
+            crate::Output::Killed { .. }
+            | crate::Output::PidReused { .. }
+            | crate::Output::Failed { .. } => {
                 panic!("This should not happen");
             }
-            crate::Output::MaybeAlreadyTerminated { process_id, source } => {
+            crate::Output::MaybeAlreadyTerminated {
+                process_id, source, ..
+            } => {
                 assert_eq!(*process_id, target_process_id);
                 assert_eq!(
                     source.to_string(),
@@ -199,23 +563,57 @@ mod tests {
     fn kill_tree_with_config_sigkill_available_max_process_id_unix() {
         let target_process_id = get_available_max_process_id();
         let config = Config {
-            signal: String::from("SIGKILL"),
+            signal: crate::Signal::SIGKILL,
             ..Default::default()
         };
         let result = kill_tree_with_config(target_process_id, &config).expect("Failed to kill");
         assert_eq!(result.len(), 1);
         let output = &result[0];
         match output {
-            crate::Output::Killed { .. } => {
+            crate::Output::Killed { .. }
+            | crate::Output::PidReused { .. }
+            | crate::Output::Failed { .. } => {
                 panic!("This should not happen");
             }
-            crate::Output::MaybeAlreadyTerminated { process_id, source } => {
+            crate::Output::MaybeAlreadyTerminated {
+                process_id, source, ..
+            } => {
                 assert_eq!(*process_id, target_process_id);
                 assert_eq!(source.to_string(), "Unix error: ESRCH: No such process");
             }
         }
     }
 
+    #[test]
+    fn kill_tree_with_config_escalation_available_max_process_id() {
+        let target_process_id = get_available_max_process_id();
+        let config = Config {
+            escalation: vec![
+                crate::EscalationStep {
+                    signal: crate::Signal::SIGTERM,
+                    timeout: std::time::Duration::from_secs(1),
+                },
+                crate::EscalationStep {
+                    signal: crate::Signal::SIGKILL,
+                    timeout: std::time::Duration::from_secs(1),
+                },
+            ],
+            ..Default::default()
+        };
+        let outputs = kill_tree_with_config(target_process_id, &config).expect("Failed to kill");
+        assert_eq!(outputs.len(), 1);
+        match &outputs[0] {
+            crate::Output::Killed { .. }
+            | crate::Output::PidReused { .. }
+            | crate::Output::Failed { .. } => {
+                panic!("This should not happen");
+            }
+            crate::Output::MaybeAlreadyTerminated { process_id, .. } => {
+                assert_eq!(*process_id, target_process_id);
+            }
+        }
+    }
+
     #[test]
     fn kill_tree_with_config_include_target_false_available_max_process_id() {
         let target_process_id = get_available_max_process_id();
@@ -226,4 +624,53 @@ mod tests {
         let result = kill_tree_with_config(target_process_id, &config).expect("Failed to kill");
         assert_eq!(result.len(), 0);
     }
+
+    #[test]
+    fn kill_trees_available_max_process_id() {
+        let target_process_ids = [
+            get_available_max_process_id(),
+            get_available_max_process_id() - 1,
+            get_available_max_process_id(),
+        ];
+        let tree_outputs =
+            kill_trees(&target_process_ids, &Config::default()).expect("Failed to kill");
+        assert_eq!(tree_outputs.len(), 2);
+        for (tree_output, target_process_id) in tree_outputs.iter().zip(target_process_ids) {
+            assert_eq!(tree_output.process_id, target_process_id);
+            assert_eq!(tree_output.outputs.len(), 1);
+            assert!(matches!(
+                tree_output.outputs[0],
+                crate::Output::MaybeAlreadyTerminated { process_id, .. } if process_id == target_process_id
+            ));
+        }
+    }
+
+    #[test]
+    fn kill_trees_invalid_process_id() {
+        let target_process_ids = [get_available_max_process_id(), 0];
+        let result = kill_trees(&target_process_ids, &Config::default());
+        assert!(matches!(result, Err(crate::Error::InvalidProcessId { .. })));
+    }
+
+    #[test]
+    fn plan_kill_tree_available_max_process_id() {
+        let target_process_id = get_available_max_process_id();
+        let kill_plan =
+            plan_kill_tree(target_process_id, &Config::default()).expect("Failed to plan");
+        assert_eq!(kill_plan.len(), 1);
+        let entry = &kill_plan[0];
+        assert_eq!(entry.process_id, target_process_id);
+        assert_eq!(entry.parent_process_id, None);
+        assert_eq!(entry.name, None);
+        assert_eq!(entry.depth, 0);
+        assert_eq!(entry.reason, crate::KillReason::Target);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn kill_cgroup_not_found() {
+        let path = std::env::temp_dir().join("kill_tree_no_such_cgroup");
+        let result = kill_cgroup(path);
+        assert!(matches!(result, Err(crate::Error::Io(_))));
+    }
 }
@@ -1,6 +1,8 @@
 use crate::core::{
-    ChildProcessIdMap, ChildProcessIdMapFilter, Config, KillOutput, Killable, KillableBuildable,
-    Output, Outputs, ProcessId, ProcessIds, ProcessInfo, ProcessInfoMap, ProcessInfos, Result,
+    ChildProcessIdMap, ChildProcessIdMapFilter, Config, ErrorPolicy, EscalationStep, ExitOutcome,
+    FilterDecision, KillOrder, KillOutput, KillPlan, KillPlanEntry, KillReason, Killable,
+    KillableBuildable, Output, Outputs, ProcessId, ProcessIds, ProcessInfo, ProcessInfoMap,
+    ProcessInfos, ProcessSelector, ProcessView, Result, Selection, Signal, TreeOutputs,
 };
 use tracing::debug;
 
@@ -44,6 +46,113 @@ pub fn get_available_max_process_id() -> u32 {
     imp::AVAILABLE_MAX_PROCESS_ID
 }
 
+/// Makes the current process a child subreaper with `prctl(PR_SET_CHILD_SUBREAPER)`.  
+/// When a process in the tree exits, its children are reparented to the current process instead of init.  
+/// So `kill_tree_with_config` with `include_target: false` on the current process can find every orphaned descendant.  
+/// Call `reap_zombies` after killing, to reap the zombies reparented to the current process.  
+///
+/// # Examples
+///
+/// ```
+/// use kill_tree::{set_child_subreaper, Result};
+///
+/// fn main() -> Result<()> {
+///     set_child_subreaper()?;
+///     Ok(())
+/// }
+/// ```
+///
+/// # Errors
+///
+/// ## `Unix`
+/// Returned when `prctl` fails.  
+#[cfg(target_os = "linux")]
+pub fn set_child_subreaper() -> Result<()> {
+    crate::linux::set_child_subreaper()
+}
+
+/// Reaps the zombie children of the current process and returns their process ids.  
+/// This includes the zombies reparented to the current process by `set_child_subreaper`.  
+/// Zombies in `exclude_process_ids` are left alone.  
+/// Pass the ids of the children still held as `std::process::Child`, otherwise they are reaped too and `Child::wait` on them fails.  
+/// The zombies are always read from `/proc`, not `Config::proc_root`, since only the children in the pid namespace of the current process can be reaped.  
+///
+/// # Examples
+///
+/// ```
+/// use kill_tree::{reap_zombies, Result};
+///
+/// fn main() -> Result<()> {
+///     let mut child = std::process::Command::new("sleep").arg("0").spawn()?;
+///     let process_ids = reap_zombies(&[child.id()])?;
+///     println!("reaped: {process_ids:?}");
+///     child.wait()?;
+///     Ok(())
+/// }
+/// ```
+///
+/// # Errors
+///
+/// ## `Io`
+/// Returned when access within the Linux `/proc/` path fails.  
+///
+/// ## `Unix`
+/// Returned when `waitpid` fails.  
+#[cfg(target_os = "linux")]
+pub fn reap_zombies(exclude_process_ids: &[ProcessId]) -> Result<Vec<ProcessId>> {
+    crate::linux::reap_zombies(exclude_process_ids)
+}
+
+pub(crate) fn parse_signal_name(name: &str) -> Option<i32> {
+    imp::parse_signal_name(name)
+}
+
+pub(crate) fn get_signal_name(number: i32) -> Option<String> {
+    imp::get_signal_name(number)
+}
+
+/// Procfs of the current pid namespace, in which the process ids of the current process are.  
+/// Read when there is no `Config::proc_root`, e.g. for the killed processes.  
+const CURRENT_PROC_ROOT: &str = "/proc";
+
+/// Reads the command line of a process provided for `Config::proc_root`.
+pub(crate) fn get_cmdline(
+    proc_root: &std::path::Path,
+    process_id: ProcessId,
+) -> Option<Vec<String>> {
+    match imp::get_read_proc_root(proc_root) {
+        Ok(x) => imp::get_cmdline(x, process_id),
+        Err(e) => {
+            debug!(process_id, error = ?e, "Failed to get proc root");
+            None
+        }
+    }
+}
+
+/// Calls `Config::filter` for the process. `Include` if it is not set.
+pub(crate) fn filter_process(
+    config: &Config,
+    process_info: &ProcessInfo,
+    depth: usize,
+) -> FilterDecision {
+    let Some(filter) = &config.filter else {
+        return FilterDecision::Include;
+    };
+    let decision = filter(&ProcessView {
+        process_info,
+        depth,
+        proc_root: &config.proc_root,
+    });
+    if decision != FilterDecision::Include {
+        debug!(
+            process_id = process_info.process_id,
+            decision = ?decision,
+            "Filtered out"
+        );
+    }
+    decision
+}
+
 /// Create a map from parent process id to child process ids.
 pub(crate) fn get_child_process_id_map(
     process_infos: &[ProcessInfo],
@@ -72,84 +181,1547 @@ pub(crate) fn get_process_info_map(process_infos: ProcessInfos) -> ProcessInfoMa
     map
 }
 
-/// Breadth-first search to get all process ids to kill.
-pub(crate) fn get_process_ids_to_kill(
+/// Breadth-first search to get all process ids to kill, paired with their depth from the target process.  
+/// `get_process_info` is used to call `Config::filter`.  
+/// The depth counts the processes skipped by the filter, so it is the same depth as passed to the filter.  
+pub(crate) fn get_process_ids_to_kill<'a>(
     target_process_id: ProcessId,
     child_process_id_map: &ChildProcessIdMap,
+    get_process_info: impl Fn(ProcessId) -> Option<&'a ProcessInfo>,
     config: &Config,
-) -> ProcessIds {
+) -> Vec<(ProcessId, usize)> {
     let mut process_ids_to_kill = Vec::new();
     let mut queue = std::collections::VecDeque::new();
-    queue.push_back(target_process_id);
-    while let Some(process_id) = queue.pop_front() {
+    queue.push_back((target_process_id, 0));
+    while let Some((process_id, depth)) = queue.pop_front() {
+        let decision = get_process_info(process_id).map_or(FilterDecision::Include, |x| {
+            filter_process(config, x, depth)
+        });
+        if decision == FilterDecision::SkipSubtree {
+            continue;
+        }
         if process_id == target_process_id {
-            if config.include_target {
-                process_ids_to_kill.push(process_id);
-            } else {
+            if !config.include_target {
                 debug!(
                     process_id,
-                    include_target = config.include_target,
-                    "Skipping target process id"
-                );
+                    include_target = config.include_target,
+                    "Skipping target process id"
+                );
+            } else if decision == FilterDecision::Include {
+                process_ids_to_kill.push((process_id, depth));
+            }
+        } else if decision == FilterDecision::Include {
+            process_ids_to_kill.push((process_id, depth));
+        }
+        if let Some(children) = child_process_id_map.get(&process_id) {
+            for &child in children {
+                queue.push_back((child, depth + 1));
+            }
+        }
+    }
+    process_ids_to_kill
+}
+
+/// Get the process ids in the tree of the target process, including the processes skipped by `Config::filter`.
+fn get_tree_process_ids(
+    target_process_id: ProcessId,
+    child_process_id_map: &ChildProcessIdMap,
+) -> std::collections::HashSet<ProcessId> {
+    let mut tree_process_ids = std::collections::HashSet::new();
+    let mut stack = vec![target_process_id];
+    while let Some(process_id) = stack.pop() {
+        // a cycle of parents is never visited twice
+        if !tree_process_ids.insert(process_id) {
+            continue;
+        }
+        if let Some(children) = child_process_id_map.get(&process_id) {
+            stack.extend(children);
+        }
+    }
+    tree_process_ids
+}
+
+/// Get the process ids in the process group or session of the target process, as selected by `Config::selection`.  
+/// The target process and the current process are not included.  
+/// The processes in the tree of the target process are not included either,
+/// since `get_process_ids_to_kill` already decided them with `Config::filter` at their depth.  
+pub(crate) fn get_member_process_ids<'a>(
+    target_process_info: Option<&ProcessInfo>,
+    process_infos: impl IntoIterator<Item = &'a ProcessInfo>,
+    child_process_id_map: &ChildProcessIdMap,
+    config: &Config,
+) -> ProcessIds {
+    let get_member_id = match config.selection {
+        Selection::Descendants => return ProcessIds::new(),
+        Selection::ProcessGroup => |x: &ProcessInfo| x.process_group_id,
+        Selection::Session => |x: &ProcessInfo| x.session_id,
+    };
+    let Some(target_process_info) = target_process_info else {
+        return ProcessIds::new();
+    };
+    let Some(member_id) = get_member_id(target_process_info) else {
+        debug!(
+            process_id = target_process_info.process_id,
+            selection = ?config.selection,
+            "Member id is None"
+        );
+        return ProcessIds::new();
+    };
+    let current_process_id = std::process::id();
+    let tree_process_ids =
+        get_tree_process_ids(target_process_info.process_id, child_process_id_map);
+    let mut member_process_ids = process_infos
+        .into_iter()
+        .filter(|x| get_member_id(x) == Some(member_id))
+        .filter(|x| !tree_process_ids.contains(&x.process_id))
+        .filter(|x| x.process_id != current_process_id)
+        .filter(|x| filter_process(config, x, 0) == FilterDecision::Include)
+        .map(|x| x.process_id)
+        .collect::<ProcessIds>();
+    member_process_ids.sort_unstable();
+    member_process_ids
+}
+
+/// Create a kill plan in the order the processes would be killed, as ordered by `Config::kill_order`.
+pub(crate) fn get_kill_plan(
+    target_process_id: ProcessId,
+    child_process_id_map: &ChildProcessIdMap,
+    process_info_map: &ProcessInfoMap,
+    config: &Config,
+) -> KillPlan {
+    let process_ids_to_kill = crate::common::get_process_ids_to_kill(
+        target_process_id,
+        child_process_id_map,
+        |x| process_info_map.get(&x),
+        config,
+    );
+    let mut kill_plan = KillPlan::new();
+    for (process_id, depth) in process_ids_to_kill {
+        let process_info = process_info_map.get(&process_id);
+        let reason = if process_id == target_process_id {
+            KillReason::Target
+        } else {
+            KillReason::Descendant
+        };
+        kill_plan.push(KillPlanEntry {
+            process_id,
+            parent_process_id: process_info.map(|x| x.parent_process_id),
+            name: process_info.map(|x| x.name.clone()),
+            depth,
+            reason,
+        });
+    }
+    let reason = match config.selection {
+        Selection::Session => KillReason::Session,
+        _ => KillReason::ProcessGroup,
+    };
+    let member_process_ids = crate::common::get_member_process_ids(
+        process_info_map.get(&target_process_id),
+        process_info_map.values(),
+        child_process_id_map,
+        config,
+    );
+    for process_id in member_process_ids {
+        let process_info = process_info_map.get(&process_id);
+        kill_plan.push(KillPlanEntry {
+            process_id,
+            parent_process_id: process_info.map(|x| x.parent_process_id),
+            name: process_info.map(|x| x.name.clone()),
+            depth: 0,
+            reason,
+        });
+    }
+    match config.kill_order {
+        KillOrder::LeavesFirst | KillOrder::LevelWaves { .. } => kill_plan.reverse(),
+        KillOrder::RootFirst => {}
+        KillOrder::PostOrderDfs => {
+            kill_plan =
+                get_post_order_kill_plan(target_process_id, child_process_id_map, kill_plan);
+        }
+    }
+    kill_plan
+}
+
+/// Reorders the kill plan in breadth-first order to depth-first post-order.  
+/// The processes included only by process group or session are placed first.  
+fn get_post_order_kill_plan(
+    target_process_id: ProcessId,
+    child_process_id_map: &ChildProcessIdMap,
+    kill_plan: KillPlan,
+) -> KillPlan {
+    let mut entry_map = std::collections::HashMap::new();
+    let mut member_entries = KillPlan::new();
+    for entry in kill_plan {
+        match entry.reason {
+            KillReason::Target | KillReason::Descendant => {
+                entry_map.insert(entry.process_id, entry);
+            }
+            KillReason::ProcessGroup | KillReason::Session => member_entries.push(entry),
+        }
+    }
+    member_entries.reverse();
+    let mut post_order_kill_plan = member_entries;
+    let mut visited = std::collections::HashSet::new();
+    // process ids with whether their children have been pushed
+    let mut stack = vec![(target_process_id, false)];
+    while let Some((process_id, children_pushed)) = stack.pop() {
+        if children_pushed {
+            if let Some(entry) = entry_map.remove(&process_id) {
+                post_order_kill_plan.push(entry);
+            }
+            continue;
+        }
+        if !visited.insert(process_id) {
+            continue;
+        }
+        stack.push((process_id, true));
+        // the processes skipped by the filter are visited, but not in the kill plan
+        if let Some(children) = child_process_id_map.get(&process_id) {
+            stack.extend(children.iter().rev().map(|x| (*x, false)));
+        }
+    }
+    post_order_kill_plan
+}
+
+/// Create a kill plan of several target processes, without duplicates of the processes in overlapping trees.  
+/// Each process is paired with the nearest target process whose tree includes it.  
+/// The processes of each target process are placed in the order of `Config::kill_order`.  
+pub(crate) fn get_trees_kill_plan(
+    target_process_ids: &[ProcessId],
+    child_process_id_map: &ChildProcessIdMap,
+    process_info_map: &ProcessInfoMap,
+    config: &Config,
+) -> Vec<(ProcessId, KillPlanEntry)> {
+    let mut owner_map = std::collections::HashMap::new();
+    let mut kill_plan = KillPlan::new();
+    for &target_process_id in target_process_ids {
+        let target_kill_plan = crate::common::get_kill_plan(
+            target_process_id,
+            child_process_id_map,
+            process_info_map,
+            config,
+        );
+        for entry in target_kill_plan {
+            // a target process in the tree of another target process
+            if !config.include_target && target_process_ids.contains(&entry.process_id) {
+                continue;
+            }
+            match owner_map.entry(entry.process_id) {
+                std::collections::hash_map::Entry::Vacant(x) => {
+                    x.insert((entry.depth, target_process_id));
+                    kill_plan.push(entry);
+                }
+                std::collections::hash_map::Entry::Occupied(mut x) => {
+                    if entry.depth < x.get().0 {
+                        x.insert((entry.depth, target_process_id));
+                    }
+                }
+            }
+        }
+    }
+    kill_plan
+        .into_iter()
+        .map(|entry| (owner_map[&entry.process_id].1, entry))
+        .collect()
+}
+
+/// Get the process ids of the current process and its ancestors, which contain the current process in their tree.
+fn get_current_process_lineage(
+    process_infos: &[ProcessInfo],
+) -> std::collections::HashSet<ProcessId> {
+    let parent_process_id_map = process_infos
+        .iter()
+        .map(|x| (x.process_id, x.parent_process_id))
+        .collect::<std::collections::HashMap<_, _>>();
+    let mut process_id = std::process::id();
+    let mut lineage = std::collections::HashSet::new();
+    // stops at the root, or at a cycle
+    while lineage.insert(process_id) {
+        let Some(&parent_process_id) = parent_process_id_map.get(&process_id) else {
+            break;
+        };
+        process_id = parent_process_id;
+    }
+    lineage
+}
+
+/// Get the process ids matched by the selector, except the ones in the tree of another matched process.  
+/// The current process, its ancestors and the processes not allowed to kill are never matched,
+/// since killing the tree of an ancestor would kill the current process.  
+pub(crate) fn select_target_process_ids(
+    selector: &ProcessSelector,
+    process_infos: &[ProcessInfo],
+    proc_root: &std::path::Path,
+) -> ProcessIds {
+    let current_process_lineage = get_current_process_lineage(process_infos);
+    let matched_process_ids = process_infos
+        .iter()
+        .filter(|x| {
+            if current_process_lineage.contains(&x.process_id) {
+                debug!(process_id = x.process_id, "Current process or its ancestor");
+                return false;
+            }
+            true
+        })
+        .filter(|x| imp::validate_process_id(x.process_id).is_ok())
+        .filter(|x| {
+            selector.matches(&ProcessView {
+                process_info: x,
+                depth: 0,
+                proc_root,
+            })
+        })
+        .map(|x| x.process_id)
+        .collect::<std::collections::HashSet<_>>();
+    let parent_process_id_map = process_infos
+        .iter()
+        .filter(|x| !imp::child_process_id_map_filter(x))
+        .map(|x| (x.process_id, x.parent_process_id))
+        .collect::<std::collections::HashMap<_, _>>();
+    let mut target_process_ids = matched_process_ids
+        .iter()
+        .copied()
+        .filter(|&process_id| {
+            let mut ancestor_process_id = process_id;
+            // bounded in case of a cycle
+            for _ in 0..parent_process_id_map.len() {
+                let Some(&parent_process_id) = parent_process_id_map.get(&ancestor_process_id)
+                else {
+                    break;
+                };
+                if matched_process_ids.contains(&parent_process_id) {
+                    debug!(
+                        process_id,
+                        ancestor_process_id = parent_process_id,
+                        "In the tree of another matched process"
+                    );
+                    return false;
+                }
+                ancestor_process_id = parent_process_id;
+            }
+            true
+        })
+        .collect::<ProcessIds>();
+    target_process_ids.sort_unstable();
+    target_process_ids
+}
+
+pub(crate) fn plan_kill_tree_internal(
+    process_id: ProcessId,
+    config: &Config,
+    process_infos: ProcessInfos,
+) -> KillPlan {
+    let child_process_id_map =
+        crate::common::get_child_process_id_map(&process_infos, imp::child_process_id_map_filter);
+    let process_info_map = crate::common::get_process_info_map(process_infos);
+    crate::common::get_kill_plan(process_id, &child_process_id_map, &process_info_map, config)
+}
+
+pub(crate) fn parse_kill_output(
+    kill_output: KillOutput,
+    process_info_map: &mut ProcessInfoMap,
+    round: usize,
+) -> Option<Output> {
+    match kill_output {
+        KillOutput::Killed { process_id } => {
+            let Some(process_info) = process_info_map.remove(&process_id) else {
+                debug!(process_id, "Process info not found");
+                return None;
+            };
+
+            Some(Output::Killed {
+                process_id: process_info.process_id,
+                parent_process_id: process_info.parent_process_id,
+                name: process_info.name.clone(),
+                ended_by_step: None,
+                exit_outcome: None,
+                round,
+                process_info,
+            })
+        }
+        KillOutput::MaybeAlreadyTerminated { process_id, source } => {
+            Some(Output::MaybeAlreadyTerminated {
+                process_id,
+                source,
+                round,
+                process_info: process_info_map.remove(&process_id),
+            })
+        }
+        KillOutput::Failed { process_id, source } => Some(Output::Failed {
+            process_id,
+            source,
+            round,
+            process_info: process_info_map.remove(&process_id),
+        }),
+    }
+}
+
+pub(crate) fn parse_pid_reused(
+    process_id: ProcessId,
+    process_info_map: &mut ProcessInfoMap,
+    round: usize,
+) -> Option<Output> {
+    let Some(process_info) = process_info_map.remove(&process_id) else {
+        debug!(process_id, "Process info not found");
+        return None;
+    };
+
+    Some(Output::PidReused {
+        process_id: process_info.process_id,
+        parent_process_id: process_info.parent_process_id,
+        name: process_info.name.clone(),
+        round,
+        process_info,
+    })
+}
+
+/// Interval to check whether the processes have exited.
+pub(crate) const EXIT_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(10);
+
+/// A process to wait for its exit, with its start time to detect a reused process id.
+#[cfg(feature = "tokio")]
+pub(crate) type ExitTarget = (ProcessId, Option<u64>);
+
+struct KillEntry {
+    process_id: ProcessId,
+    /// Target process whose tree includes the process.
+    target_process_id: ProcessId,
+    /// Start time of the process when the process infos were queried.
+    start_time: Option<u64>,
+    /// Distance from the target process.
+    depth: usize,
+    output: Option<Output>,
+    exit_outcome: ExitOutcome,
+}
+
+/// A step of `TreeKiller` whose signal is being sent.
+pub(crate) struct SignalStep {
+    index: usize,
+    signal: Signal,
+    timeout: std::time::Duration,
+}
+
+/// A process to send the signal of a step to.
+#[derive(Clone, Copy)]
+pub(crate) struct SignalTarget {
+    entry_index: usize,
+    process_id: ProcessId,
+    start_time: Option<u64>,
+}
+
+/// Sends the signal to the process, unless its process id has been reused.  
+/// Returns `None` if the process id has been reused.  
+/// With `ErrorPolicy::ContinueAndCollect`, a failure is returned as `KillOutput::Failed` instead of an error.  
+fn signal_process(
+    killable: &impl Killable,
+    proc_root: &std::path::Path,
+    target: SignalTarget,
+    error_policy: ErrorPolicy,
+) -> Result<Option<KillOutput>> {
+    let result =
+        is_process_id_reused(proc_root, target.process_id, target.start_time).and_then(|reused| {
+            if reused {
+                debug!(process_id = target.process_id, "Process id is reused");
+                return Ok(None);
+            }
+            killable.kill(target.process_id).map(Some)
+        });
+    match result {
+        Err(source) if error_policy == ErrorPolicy::ContinueAndCollect => {
+            debug!(process_id = target.process_id, error = ?source, "Failed to signal process");
+            Ok(Some(KillOutput::Failed {
+                process_id: target.process_id,
+                source,
+            }))
+        }
+        result => result,
+    }
+}
+
+/// Returns whether the process id now belongs to another process than the queried one.
+pub(crate) fn is_process_id_reused(
+    proc_root: &std::path::Path,
+    process_id: ProcessId,
+    start_time: Option<u64>,
+) -> Result<bool> {
+    let Some(start_time) = start_time else {
+        return Ok(false);
+    };
+    let current_start_time = imp::get_start_time(proc_root, process_id)?;
+    Ok(current_start_time.is_some_and(|x| x != start_time))
+}
+
+/// Max number of times to re-query the process infos to stop the processes forked during freezing.
+const MAX_FREEZE_ROUNDS: usize = 10;
+
+struct FrozenEntry {
+    process_id: ProcessId,
+    start_time: Option<u64>,
+}
+
+/// Stops the processes of a tree with `SIGSTOP`, so that they cannot fork while being killed.  
+/// Parents are stopped before their children.  
+/// The current process is never stopped.  
+#[derive(Default)]
+pub(crate) struct TreeFreezer {
+    /// In the order they were stopped.
+    entries: Vec<FrozenEntry>,
+    /// Procfs to read the stopped processes from, set by `freeze`.
+    proc_root: std::path::PathBuf,
+}
+
+impl TreeFreezer {
+    /// Stops the processes of the trees that are not stopped yet.  
+    /// Returns the number of newly stopped processes.  
+    pub(crate) fn freeze(
+        &mut self,
+        target_process_ids: &[ProcessId],
+        config: &Config,
+        process_infos: &[ProcessInfo],
+    ) -> Result<usize> {
+        self.proc_root = imp::get_signal_proc_root(&config.proc_root)?.to_path_buf();
+        let child_process_id_map = crate::common::get_child_process_id_map(
+            process_infos,
+            imp::child_process_id_map_filter,
+        );
+        let process_info_map = process_infos
+            .iter()
+            .map(|x| (x.process_id, x))
+            .collect::<std::collections::HashMap<_, _>>();
+        let start_time_map = process_infos
+            .iter()
+            .map(|x| (x.process_id, x.start_time))
+            .collect::<std::collections::HashMap<_, _>>();
+        // parents first
+        let mut process_ids_to_stop = ProcessIds::new();
+        for &target_process_id in target_process_ids {
+            let tree_process_ids = crate::common::get_process_ids_to_kill(
+                target_process_id,
+                &child_process_id_map,
+                |x| process_info_map.get(&x).copied(),
+                config,
+            )
+            .into_iter()
+            .map(|(process_id, _)| process_id);
+            let member_process_ids = crate::common::get_member_process_ids(
+                process_info_map.get(&target_process_id).copied(),
+                process_infos,
+                &child_process_id_map,
+                config,
+            );
+            for process_id in tree_process_ids.chain(member_process_ids) {
+                if !process_ids_to_stop.contains(&process_id) {
+                    process_ids_to_stop.push(process_id);
+                }
+            }
+        }
+        let process_ids_to_stop = process_ids_to_stop
+            .into_iter()
+            .filter(|x| start_time_map.contains_key(x))
+            .filter(|x| *x != std::process::id())
+            .filter(|x| self.entries.iter().all(|entry| entry.process_id != *x))
+            .collect::<ProcessIds>();
+        let killer_builder = imp::KillerBuilder::new(&process_ids_to_stop);
+        let killable = killer_builder.new_killable(Signal::SIGSTOP)?;
+        let mut count = 0;
+        for process_id in process_ids_to_stop {
+            let start_time = start_time_map.get(&process_id).copied().flatten();
+            if is_process_id_reused(&self.proc_root, process_id, start_time)? {
+                debug!(process_id, "Process id is reused");
+                continue;
+            }
+            let kill_output = match killable.kill(process_id) {
+                Ok(x) => x,
+                // not stopped, and reported as `Output::Failed` if the signal fails too
+                Err(e) if config.error_policy == ErrorPolicy::ContinueAndCollect => {
+                    debug!(process_id, error = ?e, "Failed to stop process");
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            if let KillOutput::Killed { .. } = kill_output {
+                self.entries.push(FrozenEntry {
+                    process_id,
+                    start_time,
+                });
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Continues the stopped processes with `SIGCONT`, so that the signal sent while stopped is handled.  
+    /// Children are continued before their parents.  
+    /// Errors are ignored, because the processes may have already been killed.  
+    pub(crate) fn thaw(&mut self) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let entries = std::mem::take(&mut self.entries);
+        let process_ids = entries.iter().map(|x| x.process_id).collect::<ProcessIds>();
+        let killer_builder = imp::KillerBuilder::new(&process_ids);
+        let killable = match killer_builder.new_killable(Signal::SIGCONT) {
+            Ok(x) => x,
+            Err(e) => {
+                debug!(error = ?e, "Failed to create killer");
+                return;
+            }
+        };
+        for entry in entries.iter().rev() {
+            match is_process_id_reused(&self.proc_root, entry.process_id, entry.start_time) {
+                Ok(false) => {}
+                Ok(true) => continue,
+                Err(e) => {
+                    debug!(process_id = entry.process_id, error = ?e, "Failed to check process id");
+                    continue;
+                }
+            }
+            if let Err(e) = killable.kill(entry.process_id) {
+                debug!(process_id = entry.process_id, error = ?e, "Failed to continue process");
+            }
+        }
+    }
+}
+
+/// Kills the processes of a kill plan.  
+/// Sends the signals of `Config::escalation` step by step, only to the processes that are still alive.  
+/// Without escalation, `Config::signal` is sent once.  
+/// Waiting is done by the caller, so that blocking and tokio behave the same.  
+pub(crate) struct TreeKiller {
+    steps: Vec<EscalationStep>,
+    escalate: bool,
+    wait_for_exit: Option<std::time::Duration>,
+    /// Delay after the processes of each depth are signalled. See `KillOrder::LevelWaves`.
+    level_delay: Option<std::time::Duration>,
+    error_policy: ErrorPolicy,
+    next_step: usize,
+    entries: Vec<KillEntry>,
+    process_info_map: ProcessInfoMap,
+    killer_builder: std::sync::Arc<imp::KillerBuilder>,
+    /// Procfs to read the processes to kill from. See `Config::proc_root`.
+    proc_root: std::path::PathBuf,
+    round: usize,
+    /// Set if the processes are killed by `cgroup.kill`.
+    #[cfg(target_os = "linux")]
+    cgroup_path: Option<std::path::PathBuf>,
+}
+
+impl TreeKiller {
+    /// Kills the trees of the target processes together.  
+    /// From the second round, only the processes that are found and still alive are killed.  
+    pub(crate) fn new(
+        target_process_ids: &[ProcessId],
+        config: &Config,
+        process_infos: ProcessInfos,
+        round: usize,
+    ) -> Result<Self> {
+        let child_process_id_map = crate::common::get_child_process_id_map(
+            &process_infos,
+            imp::child_process_id_map_filter,
+        );
+        let proc_root = imp::get_signal_proc_root(&config.proc_root)?.to_path_buf();
+        let mut process_info_map = crate::common::get_process_info_map(process_infos);
+        let mut kill_plan = crate::common::get_trees_kill_plan(
+            target_process_ids,
+            &child_process_id_map,
+            &process_info_map,
+            config,
+        );
+        if round > 0 {
+            let mut live_kill_plan = Vec::new();
+            for (target_process_id, entry) in kill_plan {
+                if entry.parent_process_id.is_some()
+                    && imp::get_exit_outcome(&proc_root, entry.process_id)?
+                        == ExitOutcome::StillAlive
+                {
+                    live_kill_plan.push((target_process_id, entry));
+                }
+            }
+            kill_plan = live_kill_plan;
+        }
+        let escalate = !config.escalation.is_empty();
+        let steps = if escalate {
+            config.escalation.clone()
+        } else {
+            vec![EscalationStep {
+                signal: config.signal,
+                timeout: std::time::Duration::ZERO,
+            }]
+        };
+        // the target process that was not found is killed without a pidfd
+        let process_ids = kill_plan
+            .iter()
+            .filter(|(_, entry)| entry.parent_process_id.is_some())
+            .map(|(_, entry)| entry.process_id)
+            .collect::<ProcessIds>();
+        // queried only for the processes to kill, before any signal is sent
+        for process_id in &process_ids {
+            if let Some(process_info) = process_info_map.get_mut(process_id) {
+                process_info.cmdline = imp::get_cmdline(&proc_root, *process_id);
+                process_info.exe = imp::get_exe(&proc_root, *process_id);
+                // already set from the procfs of another pid namespace
+                if process_info.namespace_process_ids.is_none() {
+                    (
+                        process_info.namespace_process_ids,
+                        process_info.namespace_process_group_ids,
+                    ) = imp::get_namespace_process_ids(&proc_root, *process_id);
+                }
+            }
+        }
+        let killer_builder = std::sync::Arc::new(imp::KillerBuilder::new(&process_ids));
+        // only a single tree can match a cgroup
+        #[cfg(target_os = "linux")]
+        let cgroup_path = match (config.cgroup.as_ref(), target_process_ids) {
+            (Some(cgroup_config), [target_process_id]) => crate::linux::get_cgroup_path_to_kill(
+                &proc_root,
+                &cgroup_config.root,
+                *target_process_id,
+                &process_ids,
+            ),
+            _ => None,
+        };
+        let entries = kill_plan
+            .iter()
+            .map(|(target_process_id, entry)| KillEntry {
+                process_id: entry.process_id,
+                target_process_id: *target_process_id,
+                start_time: process_info_map
+                    .get(&entry.process_id)
+                    .and_then(|x| x.start_time),
+                depth: entry.depth,
+                output: None,
+                exit_outcome: ExitOutcome::StillAlive,
+            })
+            .collect();
+        Ok(Self {
+            steps,
+            escalate,
+            wait_for_exit: config.wait_for_exit,
+            level_delay: match config.kill_order {
+                KillOrder::LevelWaves { delay } => Some(delay),
+                _ => None,
+            },
+            error_policy: config.error_policy,
+            next_step: 0,
+            entries,
+            process_info_map,
+            killer_builder,
+            proc_root,
+            round,
+            #[cfg(target_os = "linux")]
+            cgroup_path,
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Marks every process as killed, after they are killed at once without steps.
+    #[cfg(target_os = "linux")]
+    fn kill_all(&mut self) {
+        self.next_step = self.steps.len();
+        for entry in &mut self.entries {
+            entry.output = crate::common::parse_kill_output(
+                KillOutput::Killed {
+                    process_id: entry.process_id,
+                },
+                &mut self.process_info_map,
+                self.round,
+            );
+        }
+    }
+
+    /// Sends the signal of the next step to the processes that are still alive.  
+    /// Processes whose process id has been reused since the process infos were queried are skipped.  
+    /// Returns the timeout to wait for the processes to exit, or `None` if there is no step left.  
+    #[cfg(feature = "blocking")]
+    pub(crate) fn signal_next_step(&mut self) -> Result<Option<std::time::Duration>> {
+        let Some(step) = self.begin_next_step() else {
+            return Ok(None);
+        };
+        let killable = self.killer_builder.new_killable(step.signal)?;
+        let mut kill_outputs = Vec::new();
+        for (level_index, level) in self.signal_levels().into_iter().enumerate() {
+            if let Some(delay) = self.level_delay.filter(|_| level_index > 0) {
+                std::thread::sleep(delay);
+            }
+            for target in level {
+                kill_outputs.push((
+                    target.entry_index,
+                    signal_process(&killable, &self.proc_root, target, self.error_policy)?,
+                ));
+            }
+        }
+        Ok(self.end_step(&step, kill_outputs))
+    }
+
+    /// Starts the next step, or kills the cgroup at once if set.  
+    /// Returns `None` if there is no step left or no process is alive.  
+    fn begin_next_step(&mut self) -> Option<SignalStep> {
+        #[cfg(target_os = "linux")]
+        if let Some(cgroup_path) = self.cgroup_path.take() {
+            match crate::linux::write_cgroup_kill(&cgroup_path) {
+                Ok(()) => {
+                    self.kill_all();
+                    return None;
+                }
+                Err(e) => {
+                    debug!(cgroup_path = ?cgroup_path, error = ?e, "Failed to kill cgroup, signals are sent instead");
+                }
+            }
+        }
+        let step = self.steps.get(self.next_step)?;
+        if !self.is_any_alive() {
+            return None;
+        }
+        let step = SignalStep {
+            index: self.next_step,
+            signal: step.signal,
+            timeout: step.timeout,
+        };
+        self.next_step += 1;
+        Some(step)
+    }
+
+    /// Returns the processes that are still alive, split into levels of the same depth.  
+    /// The levels are in the order to kill, so every process of a level can be signalled at the same time.  
+    fn signal_levels(&self) -> Vec<Vec<SignalTarget>> {
+        let mut levels: Vec<Vec<SignalTarget>> = Vec::new();
+        let mut last_depth = None;
+        for (entry_index, entry) in self.entries.iter().enumerate() {
+            if entry.exit_outcome != ExitOutcome::StillAlive {
+                continue;
+            }
+            let target = SignalTarget {
+                entry_index,
+                process_id: entry.process_id,
+                start_time: entry.start_time,
+            };
+            match levels.last_mut() {
+                Some(level) if last_depth == Some(entry.depth) => level.push(target),
+                _ => levels.push(vec![target]),
+            }
+            last_depth = Some(entry.depth);
+        }
+        levels
+    }
+
+    /// Records the results of signalling the processes in the step.  
+    /// `None` means that the process id has been reused. A process that failed is not signalled again.  
+    /// Returns the timeout to wait for the processes to exit, or `None` if there is no step left.  
+    fn end_step(
+        &mut self,
+        step: &SignalStep,
+        kill_outputs: Vec<(usize, Option<KillOutput>)>,
+    ) -> Option<std::time::Duration> {
+        for (entry_index, kill_output) in kill_outputs {
+            let entry = &mut self.entries[entry_index];
+            if !matches!(kill_output, Some(KillOutput::Killed { .. })) {
+                entry.exit_outcome = ExitOutcome::Exited;
+            }
+            match (&mut entry.output, kill_output) {
+                (None, Some(kill_output)) => {
+                    entry.output = crate::common::parse_kill_output(
+                        kill_output,
+                        &mut self.process_info_map,
+                        self.round,
+                    );
+                }
+                (None, None) => {
+                    entry.output = crate::common::parse_pid_reused(
+                        entry.process_id,
+                        &mut self.process_info_map,
+                        self.round,
+                    );
+                }
+                // the signal of a previous step was sent, but the one of this step failed
+                (Some(output), Some(KillOutput::Failed { process_id, source })) => {
+                    let process_info = output.process_info().cloned();
+                    *output = Output::Failed {
+                        process_id,
+                        source,
+                        round: self.round,
+                        process_info,
+                    };
+                }
+                (Some(Output::Killed { ended_by_step, .. }), _)
+                    if entry.exit_outcome == ExitOutcome::Exited =>
+                {
+                    // exited after the signal of the previous step
+                    *ended_by_step = step.index.checked_sub(1);
+                }
+                (Some(_), _) => {}
+            }
+        }
+        if self.escalate {
+            Some(step.timeout)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the timeout of `Config::wait_for_exit`.
+    pub(crate) fn wait_for_exit(&self) -> Option<std::time::Duration> {
+        self.wait_for_exit
+    }
+
+    /// Checks whether the processes signalled so far have exited.  
+    /// Returns `true` if any process is still alive.  
+    pub(crate) fn update_exit_outcomes(&mut self) -> Result<bool> {
+        let ended_by_step = self.next_step.checked_sub(1).filter(|_| self.escalate);
+        for entry in self
+            .entries
+            .iter_mut()
+            .filter(|entry| entry.exit_outcome != ExitOutcome::Exited)
+        {
+            // the queried process has exited if another process has the process id now
+            let exit_outcome =
+                if is_process_id_reused(&self.proc_root, entry.process_id, entry.start_time)? {
+                    ExitOutcome::Exited
+                } else {
+                    imp::get_exit_outcome(&self.proc_root, entry.process_id)?
+                };
+            if entry.exit_outcome == ExitOutcome::StillAlive
+                && exit_outcome != ExitOutcome::StillAlive
+            {
+                if let Some(Output::Killed {
+                    ended_by_step: x, ..
+                }) = &mut entry.output
+                {
+                    *x = ended_by_step;
+                }
+            }
+            entry.exit_outcome = exit_outcome;
+        }
+        Ok(self.is_any_alive())
+    }
+
+    fn is_any_alive(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.exit_outcome == ExitOutcome::StillAlive)
+    }
+
+    /// Returns the outputs paired with their target process.
+    pub(crate) fn into_outputs(self) -> Vec<(ProcessId, Output)> {
+        let wait_for_exit = self.wait_for_exit.is_some();
+        self.entries
+            .into_iter()
+            .filter_map(|entry| {
+                let mut output = entry.output?;
+                if let Output::Killed { exit_outcome, .. } = &mut output {
+                    if wait_for_exit {
+                        *exit_outcome = Some(entry.exit_outcome);
+                    }
+                }
+                Some((entry.target_process_id, output))
+            })
+            .collect()
+    }
+}
+
+/// Repeats rounds of querying and killing, as set by `Config::repeat`.
+pub(crate) struct Rounds {
+    /// Without duplicates, in the given order.
+    target_process_ids: ProcessIds,
+    /// Target processes whose process id has not been reused.
+    live_target_process_ids: ProcessIds,
+    max_count: usize,
+    deadline: Option<std::time::Instant>,
+    next_round: usize,
+    /// Start times of the target processes in the first round.
+    start_time_map: std::collections::HashMap<ProcessId, Option<u64>>,
+    /// Paired with their target process.
+    outputs: Vec<(ProcessId, Output)>,
+}
+
+impl Rounds {
+    pub(crate) fn new(target_process_ids: &[ProcessId], config: &Config) -> Self {
+        let mut unique_target_process_ids = ProcessIds::new();
+        for &target_process_id in target_process_ids {
+            if !unique_target_process_ids.contains(&target_process_id) {
+                unique_target_process_ids.push(target_process_id);
+            }
+        }
+        let now = std::time::Instant::now();
+        let (max_count, deadline) = match &config.repeat {
+            Some(repeat_limit) => (
+                repeat_limit.max_rounds,
+                repeat_limit.deadline.map(|x| now + x),
+            ),
+            None => (1, None),
+        };
+        Self {
+            live_target_process_ids: unique_target_process_ids.clone(),
+            target_process_ids: unique_target_process_ids,
+            max_count,
+            deadline,
+            next_round: 0,
+            start_time_map: std::collections::HashMap::new(),
+            outputs: Vec::new(),
+        }
+    }
+
+    /// Returns whether another round can be started within the limits.  
+    /// The first round is always started.  
+    pub(crate) fn has_next(&self) -> bool {
+        if self.next_round == 0 {
+            return true;
+        }
+        self.next_round < self.max_count
+            && self.deadline.is_none_or(|x| std::time::Instant::now() < x)
+    }
+
+    /// Starts the next round with the queried process infos.  
+    /// Returns `None` if no more round is needed, because no live process is found,
+    /// or the process ids of all target processes have been reused.  
+    pub(crate) fn start_next(
+        &mut self,
+        config: &Config,
+        process_infos: ProcessInfos,
+    ) -> Result<Option<TreeKiller>> {
+        let round = self.next_round;
+        self.next_round += 1;
+        let get_start_time = |process_id: ProcessId| {
+            process_infos
+                .iter()
+                .find(|x| x.process_id == process_id)
+                .and_then(|x| x.start_time)
+        };
+        if round == 0 {
+            for &target_process_id in &self.target_process_ids {
+                self.start_time_map
+                    .insert(target_process_id, get_start_time(target_process_id));
+            }
+        } else {
+            let start_time_map = &self.start_time_map;
+            self.live_target_process_ids.retain(|&target_process_id| {
+                let first_start_time = start_time_map.get(&target_process_id).copied().flatten();
+                let start_time = get_start_time(target_process_id);
+                let is_reused = first_start_time.is_some()
+                    && start_time.is_some()
+                    && first_start_time != start_time;
+                if is_reused {
+                    debug!(process_id = target_process_id, "Process id is reused");
+                }
+                !is_reused
+            });
+            if self.live_target_process_ids.is_empty() {
+                return Ok(None);
+            }
+        }
+        let tree_killer =
+            TreeKiller::new(&self.live_target_process_ids, config, process_infos, round)?;
+        if round > 0 && tree_killer.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(tree_killer))
+    }
+
+    pub(crate) fn finish(&mut self, tree_killer: TreeKiller) {
+        self.outputs.extend(tree_killer.into_outputs());
+    }
+
+    pub(crate) fn live_target_process_ids(&self) -> &[ProcessId] {
+        &self.live_target_process_ids
+    }
+
+    pub(crate) fn into_outputs(self) -> Outputs {
+        self.outputs.into_iter().map(|(_, output)| output).collect()
+    }
+
+    /// Returns the outputs grouped by their target process, in the order of the target processes.
+    pub(crate) fn into_tree_outputs(self) -> Vec<TreeOutputs> {
+        let mut tree_outputs = self
+            .target_process_ids
+            .iter()
+            .map(|&process_id| TreeOutputs {
+                process_id,
+                outputs: Outputs::new(),
+            })
+            .collect::<Vec<_>>();
+        for (target_process_id, output) in self.outputs {
+            if let Some(x) = tree_outputs
+                .iter_mut()
+                .find(|x| x.process_id == target_process_id)
+            {
+                x.outputs.push(output);
+            }
+        }
+        tree_outputs
+    }
+}
+
+#[cfg(feature = "blocking")]
+pub(crate) mod blocking {
+    use super::{
+        Config, Outputs, ProcessId, ProcessIds, ProcessInfos, ProcessSelector, Result, Rounds,
+        TreeFreezer, TreeKiller, TreeOutputs,
+    };
+    use crate::core::blocking::ProcessInfosProvidable;
+
+    /// Queries the process infos.  
+    /// If `Config::freeze` is set, stops the processes of the tree, re-querying the process infos until no new process is found.  
+    fn query_process_infos(
+        tree_freezer: &mut TreeFreezer,
+        target_process_ids: &[ProcessId],
+        config: &Config,
+        process_infos_provider: &impl ProcessInfosProvidable,
+    ) -> Result<ProcessInfos> {
+        let mut process_infos = process_infos_provider.get_process_infos()?;
+        if !config.freeze || cfg!(windows) {
+            return Ok(process_infos);
+        }
+        for _ in 0..super::MAX_FREEZE_ROUNDS {
+            if tree_freezer.freeze(target_process_ids, config, &process_infos)? == 0 {
+                break;
+            }
+            // children forked before their parent was stopped
+            process_infos = process_infos_provider.get_process_infos()?;
+        }
+        Ok(process_infos)
+    }
+
+    /// Waits until every signalled process has exited or the timeout elapses.
+    fn wait_for_exit(tree_killer: &mut TreeKiller, timeout: std::time::Duration) -> Result<()> {
+        let deadline = std::time::Instant::now() + timeout;
+        while tree_killer.update_exit_outcomes()? {
+            let now = std::time::Instant::now();
+            if now >= deadline {
+                break;
+            }
+            std::thread::sleep(super::EXIT_POLL_INTERVAL.min(deadline - now));
+        }
+        Ok(())
+    }
+
+    fn kill_round(tree_killer: &mut TreeKiller, tree_freezer: &mut TreeFreezer) -> Result<()> {
+        let result = tree_killer.signal_next_step();
+        tree_freezer.thaw();
+        let mut timeout = result?;
+        while let Some(x) = timeout {
+            wait_for_exit(tree_killer, x)?;
+            timeout = tree_killer.signal_next_step()?;
+        }
+        if let Some(timeout) = tree_killer.wait_for_exit() {
+            wait_for_exit(tree_killer, timeout)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn kill_tree_internal(
+        process_id: ProcessId,
+        config: &Config,
+        process_infos_provider: &impl ProcessInfosProvidable,
+    ) -> Result<Outputs> {
+        let rounds = kill_rounds(&[process_id], config, process_infos_provider)?;
+        Ok(rounds.into_outputs())
+    }
+
+    pub(crate) fn kill_trees_internal(
+        process_ids: &[ProcessId],
+        config: &Config,
+        process_infos_provider: &impl ProcessInfosProvidable,
+    ) -> Result<Vec<TreeOutputs>> {
+        let rounds = kill_rounds(process_ids, config, process_infos_provider)?;
+        Ok(rounds.into_tree_outputs())
+    }
+
+    /// Provides the given process infos first, and then queries them.
+    struct SnapshotProcessInfosProvider<'a, T: ProcessInfosProvidable> {
+        process_infos: std::sync::Mutex<Option<ProcessInfos>>,
+        process_infos_provider: &'a T,
+    }
+
+    impl<T: ProcessInfosProvidable> ProcessInfosProvidable for SnapshotProcessInfosProvider<'_, T> {
+        fn get_process_infos(&self) -> Result<ProcessInfos> {
+            let process_infos = match self.process_infos.lock() {
+                Ok(mut x) => x.take(),
+                Err(e) => e.into_inner().take(),
+            };
+            match process_infos {
+                Some(x) => Ok(x),
+                None => self.process_infos_provider.get_process_infos(),
+            }
+        }
+    }
+
+    pub(crate) fn select_process_ids_internal(
+        selector: &ProcessSelector,
+        process_infos_provider: &impl ProcessInfosProvidable,
+    ) -> Result<ProcessIds> {
+        let process_infos = process_infos_provider.get_process_infos()?;
+        Ok(super::select_target_process_ids(
+            selector,
+            &process_infos,
+            std::path::Path::new(super::CURRENT_PROC_ROOT),
+        ))
+    }
+
+    /// Kills the trees of the processes matched by the selector.  
+    /// The process infos queried to match are also used to kill in the first round.  
+    pub(crate) fn kill_selected_trees_internal(
+        selector: &ProcessSelector,
+        config: &Config,
+        process_infos_provider: &impl ProcessInfosProvidable,
+    ) -> Result<Vec<TreeOutputs>> {
+        let process_infos = process_infos_provider.get_process_infos()?;
+        let process_ids =
+            super::select_target_process_ids(selector, &process_infos, &config.proc_root);
+        if process_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let process_infos_provider = SnapshotProcessInfosProvider {
+            process_infos: std::sync::Mutex::new(Some(process_infos)),
+            process_infos_provider,
+        };
+        let rounds = kill_rounds(&process_ids, config, &process_infos_provider)?;
+        Ok(rounds.into_tree_outputs())
+    }
+
+    /// Kills the trees of the target processes together, in rounds.
+    fn kill_rounds(
+        process_ids: &[ProcessId],
+        config: &Config,
+        process_infos_provider: &impl ProcessInfosProvidable,
+    ) -> Result<Rounds> {
+        let mut rounds = Rounds::new(process_ids, config);
+        while rounds.has_next() {
+            let mut tree_freezer = TreeFreezer::default();
+            let result = query_process_infos(
+                &mut tree_freezer,
+                rounds.live_target_process_ids(),
+                config,
+                process_infos_provider,
+            )
+            .and_then(|process_infos| rounds.start_next(config, process_infos));
+            let mut tree_killer = match result {
+                Ok(Some(x)) => x,
+                Ok(None) => {
+                    tree_freezer.thaw();
+                    break;
+                }
+                Err(e) => {
+                    tree_freezer.thaw();
+                    return Err(e);
+                }
+            };
+            kill_round(&mut tree_killer, &mut tree_freezer)?;
+            rounds.finish(tree_killer);
+        }
+        Ok(rounds)
+    }
+}
+
+#[cfg(feature = "tokio")]
+pub(crate) mod tokio {
+    use super::{
+        imp, Config, ExitOutcome, ExitTarget, KillOutput, KillableBuildable, Output, Outputs,
+        ProcessId, ProcessIds, ProcessInfos, ProcessSelector, Result, Rounds, TreeFreezer,
+        TreeKiller, TreeOutputs,
+    };
+    use crate::core::{tokio::ProcessInfosProvidable, Error, ProcessExit};
+
+    /// Resumes the panic of the task, or returns the error if the task was cancelled.
+    pub(crate) fn from_join_error(error: ::tokio::task::JoinError) -> Error {
+        if error.is_panic() {
+            std::panic::resume_unwind(error.into_panic());
+        }
+        std::io::Error::other(error).into()
+    }
+
+    /// Sends the signal of the next step like `TreeKiller::signal_next_step`, without blocking the runtime.  
+    /// The processes of each level are signalled at the same time by up to `Config::signal_concurrency` blocking tasks,
+    /// and the next level is signalled after every process of the level is signalled.  
+    /// With `ErrorPolicy::FailFast`, the other tasks stop at the first error, and every task is awaited before it is returned.  
+    pub(crate) async fn signal_next_step(
+        tree_killer: &mut TreeKiller,
+        config: &Config,
+    ) -> Result<Option<std::time::Duration>> {
+        let Some(step) = tree_killer.begin_next_step() else {
+            return Ok(None);
+        };
+        let mut kill_outputs = Vec::new();
+        for (level_index, level) in tree_killer.signal_levels().into_iter().enumerate() {
+            if let Some(delay) = tree_killer.level_delay.filter(|_| level_index > 0) {
+                ::tokio::time::sleep(delay).await;
+            }
+            let chunk_size = level.len().div_ceil(config.signal_concurrency.max(1));
+            let failed = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+            let mut tasks = ::tokio::task::JoinSet::new();
+            for chunk in level.chunks(chunk_size) {
+                let chunk = chunk.to_vec();
+                let killer_builder = tree_killer.killer_builder.clone();
+                let proc_root = tree_killer.proc_root.clone();
+                let signal = step.signal;
+                let error_policy = tree_killer.error_policy;
+                let failed = failed.clone();
+                tasks.spawn_blocking(move || -> Result<Vec<(usize, Option<KillOutput>)>> {
+                    let killable = killer_builder.new_killable(signal)?;
+                    let mut kill_outputs = Vec::new();
+                    for target in chunk {
+                        if failed.load(std::sync::atomic::Ordering::Relaxed) {
+                            break;
+                        }
+                        match super::signal_process(&killable, &proc_root, target, error_policy) {
+                            Ok(x) => kill_outputs.push((target.entry_index, x)),
+                            Err(e) => {
+                                failed.store(true, std::sync::atomic::Ordering::Relaxed);
+                                return Err(e);
+                            }
+                        }
+                    }
+                    Ok(kill_outputs)
+                });
+            }
+            // every task is awaited, so that no signal is sent after the error is returned
+            let mut first_error = None;
+            while let Some(result) = tasks.join_next().await {
+                match result.map_err(from_join_error).and_then(|x| x) {
+                    Ok(x) => kill_outputs.extend(x),
+                    Err(e) => {
+                        first_error.get_or_insert(e);
+                    }
+                }
+            }
+            if let Some(e) = first_error {
+                return Err(e);
+            }
+        }
+        Ok(tree_killer.end_step(&step, kill_outputs))
+    }
+
+    /// Queries the process infos.  
+    /// If `Config::freeze` is set, stops the processes of the tree, re-querying the process infos until no new process is found.  
+    async fn query_process_infos(
+        tree_freezer: &mut TreeFreezer,
+        target_process_ids: &[ProcessId],
+        config: &Config,
+        process_infos_provider: &impl ProcessInfosProvidable,
+    ) -> Result<ProcessInfos> {
+        let mut process_infos = process_infos_provider.get_process_infos().await?;
+        if !config.freeze || cfg!(windows) {
+            return Ok(process_infos);
+        }
+        for _ in 0..super::MAX_FREEZE_ROUNDS {
+            if tree_freezer.freeze(target_process_ids, config, &process_infos)? == 0 {
+                break;
+            }
+            // children forked before their parent was stopped
+            process_infos = process_infos_provider.get_process_infos().await?;
+        }
+        Ok(process_infos)
+    }
+
+    /// Waits until every signalled process has exited or the timeout elapses.
+    async fn wait_for_exit(
+        tree_killer: &mut TreeKiller,
+        timeout: std::time::Duration,
+    ) -> Result<()> {
+        let deadline = ::tokio::time::Instant::now() + timeout;
+        while tree_killer.update_exit_outcomes()? {
+            let now = ::tokio::time::Instant::now();
+            if now >= deadline {
+                break;
+            }
+            ::tokio::time::sleep(super::EXIT_POLL_INTERVAL.min(deadline - now)).await;
+        }
+        Ok(())
+    }
+
+    async fn kill_round(
+        tree_killer: &mut TreeKiller,
+        tree_freezer: &mut TreeFreezer,
+        config: &Config,
+    ) -> Result<()> {
+        let result = signal_next_step(tree_killer, config).await;
+        tree_freezer.thaw();
+        let mut timeout = result?;
+        while let Some(x) = timeout {
+            wait_for_exit(tree_killer, x).await?;
+            timeout = signal_next_step(tree_killer, config).await?;
+        }
+        if let Some(timeout) = tree_killer.wait_for_exit() {
+            wait_for_exit(tree_killer, timeout).await?;
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn kill_tree_internal(
+        process_id: ProcessId,
+        config: &Config,
+        process_infos_provider: &impl ProcessInfosProvidable,
+    ) -> Result<Outputs> {
+        let rounds = kill_rounds(&[process_id], config, process_infos_provider).await?;
+        Ok(rounds.into_outputs())
+    }
+
+    pub(crate) async fn kill_trees_internal(
+        process_ids: &[ProcessId],
+        config: &Config,
+        process_infos_provider: &impl ProcessInfosProvidable,
+    ) -> Result<Vec<TreeOutputs>> {
+        let rounds = kill_rounds(process_ids, config, process_infos_provider).await?;
+        Ok(rounds.into_tree_outputs())
+    }
+
+    /// Checks once whether the processes have exited, with their start times.  
+    /// Returns the processes still alive, and the exits of the others.  
+    pub(crate) fn poll_exits(
+        targets: Vec<ExitTarget>,
+    ) -> Result<(Vec<ExitTarget>, Vec<ProcessExit>)> {
+        let mut live_targets = Vec::new();
+        let mut exits = Vec::new();
+        // the killed process ids are the ones of the current pid namespace
+        let proc_root = std::path::Path::new(super::CURRENT_PROC_ROOT);
+        for (process_id, start_time) in targets {
+            if super::is_process_id_reused(proc_root, process_id, start_time)? {
+                exits.push(ProcessExit {
+                    process_id,
+                    exit_outcome: ExitOutcome::Exited,
+                });
+                continue;
             }
-        } else {
-            process_ids_to_kill.push(process_id);
-        }
-        if let Some(children) = child_process_id_map.get(&process_id) {
-            for &child in children {
-                queue.push_back(child);
+            match imp::get_exit_outcome(proc_root, process_id)? {
+                ExitOutcome::StillAlive => live_targets.push((process_id, start_time)),
+                exit_outcome => exits.push(ProcessExit {
+                    process_id,
+                    exit_outcome,
+                }),
             }
         }
+        Ok((live_targets, exits))
     }
-    process_ids_to_kill
-}
-
-pub(crate) fn parse_kill_output(
-    kill_output: KillOutput,
-    process_info_map: &mut ProcessInfoMap,
-) -> Option<Output> {
-    match kill_output {
-        KillOutput::Killed { process_id } => {
-            let Some(process_info) = process_info_map.remove(&process_id) else {
-                debug!(process_id, "Process info not found");
-                return None;
-            };
 
-            Some(Output::Killed {
-                process_id: process_info.process_id,
-                parent_process_id: process_info.parent_process_id,
-                name: process_info.name,
+    /// Waits until every killed process of the outputs exits or the timeout elapses.  
+    /// Returns the exits in the order the processes exit, followed by the processes still alive.  
+    pub(crate) async fn wait_tree_exit_internal(
+        outputs: &Outputs,
+        timeout: std::time::Duration,
+    ) -> Result<Vec<ProcessExit>> {
+        let deadline = ::tokio::time::Instant::now() + timeout;
+        // with the start time, a process whose process id was reused is not waited on
+        let mut targets = outputs
+            .iter()
+            .filter_map(|output| match output {
+                Output::Killed {
+                    process_id,
+                    process_info,
+                    ..
+                } => Some((*process_id, process_info.start_time)),
+                _ => None,
             })
+            .collect::<Vec<_>>();
+        let mut process_exits = Vec::new();
+        #[cfg(target_os = "linux")]
+        if crate::linux::tokio::wait_exits(&targets, deadline, &mut process_exits).await? {
+            return Ok(process_exits);
         }
-        KillOutput::MaybeAlreadyTerminated { process_id, source } => {
-            Some(Output::MaybeAlreadyTerminated { process_id, source })
+        loop {
+            let (live_targets, exits) = ::tokio::task::spawn_blocking(move || poll_exits(targets))
+                .await
+                .map_err(from_join_error)??;
+            targets = live_targets;
+            process_exits.extend(exits);
+            let now = ::tokio::time::Instant::now();
+            if targets.is_empty() || now >= deadline {
+                break;
+            }
+            ::tokio::time::sleep(super::EXIT_POLL_INTERVAL.min(deadline - now)).await;
         }
+        process_exits.extend(targets.into_iter().map(|(process_id, _)| ProcessExit {
+            process_id,
+            exit_outcome: ExitOutcome::StillAlive,
+        }));
+        Ok(process_exits)
     }
-}
 
-pub(crate) fn kill_tree_internal(
-    process_id: ProcessId,
-    config: &Config,
-    process_infos: ProcessInfos,
-) -> Result<Outputs> {
-    let child_process_id_map =
-        crate::common::get_child_process_id_map(&process_infos, imp::child_process_id_map_filter);
-    let process_ids_to_kill =
-        crate::common::get_process_ids_to_kill(process_id, &child_process_id_map, config);
-    let killable_builder = imp::KillerBuilder {};
-    let killable = killable_builder.new_killable(config)?;
-    let mut outputs = Outputs::new();
-    let mut process_info_map = crate::common::get_process_info_map(process_infos);
-    // kill children first
-    for &process_id in process_ids_to_kill.iter().rev() {
-        let kill_output = killable.kill(process_id)?;
-        let Some(output) = crate::common::parse_kill_output(kill_output, &mut process_info_map)
-        else {
-            continue;
+    /// Provides the given process infos first, and then queries them.
+    struct SnapshotProcessInfosProvider<'a, T: ProcessInfosProvidable> {
+        process_infos: std::sync::Mutex<Option<ProcessInfos>>,
+        process_infos_provider: &'a T,
+    }
+
+    impl<T: ProcessInfosProvidable> ProcessInfosProvidable for SnapshotProcessInfosProvider<'_, T> {
+        async fn get_process_infos(&self) -> Result<ProcessInfos> {
+            let process_infos = match self.process_infos.lock() {
+                Ok(mut x) => x.take(),
+                Err(e) => e.into_inner().take(),
+            };
+            match process_infos {
+                Some(x) => Ok(x),
+                None => self.process_infos_provider.get_process_infos().await,
+            }
+        }
+    }
+
+    pub(crate) async fn select_process_ids_internal(
+        selector: &ProcessSelector,
+        process_infos_provider: &impl ProcessInfosProvidable,
+    ) -> Result<ProcessIds> {
+        let process_infos = process_infos_provider.get_process_infos().await?;
+        Ok(super::select_target_process_ids(
+            selector,
+            &process_infos,
+            std::path::Path::new(super::CURRENT_PROC_ROOT),
+        ))
+    }
+
+    /// Kills the trees of the processes matched by the selector.  
+    /// The process infos queried to match are also used to kill in the first round.  
+    pub(crate) async fn kill_selected_trees_internal(
+        selector: &ProcessSelector,
+        config: &Config,
+        process_infos_provider: &impl ProcessInfosProvidable,
+    ) -> Result<Vec<TreeOutputs>> {
+        let process_infos = process_infos_provider.get_process_infos().await?;
+        let process_ids =
+            super::select_target_process_ids(selector, &process_infos, &config.proc_root);
+        if process_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let process_infos_provider = SnapshotProcessInfosProvider {
+            process_infos: std::sync::Mutex::new(Some(process_infos)),
+            process_infos_provider,
         };
-        outputs.push(output);
+        let rounds = kill_rounds(&process_ids, config, &process_infos_provider).await?;
+        Ok(rounds.into_tree_outputs())
+    }
+
+    /// Kills the trees of the target processes together, in rounds.
+    async fn kill_rounds(
+        process_ids: &[ProcessId],
+        config: &Config,
+        process_infos_provider: &impl ProcessInfosProvidable,
+    ) -> Result<Rounds> {
+        let mut rounds = Rounds::new(process_ids, config);
+        while rounds.has_next() {
+            let mut tree_freezer = TreeFreezer::default();
+            let result = query_process_infos(
+                &mut tree_freezer,
+                rounds.live_target_process_ids(),
+                config,
+                process_infos_provider,
+            )
+            .await
+            .and_then(|process_infos| rounds.start_next(config, process_infos));
+            let mut tree_killer = match result {
+                Ok(Some(x)) => x,
+                Ok(None) => {
+                    tree_freezer.thaw();
+                    break;
+                }
+                Err(e) => {
+                    tree_freezer.thaw();
+                    return Err(e);
+                }
+            };
+            kill_round(&mut tree_killer, &mut tree_freezer, config).await?;
+            rounds.finish(tree_killer);
+        }
+        Ok(rounds)
     }
-    Ok(outputs)
 }
 
 #[cfg(test)]
@@ -181,16 +1753,19 @@ mod tests {
                 process_id: 1,
                 parent_process_id: 0,
                 name: "1".to_string(),
+                ..Default::default()
             },
             ProcessInfo {
                 process_id: 2,
                 parent_process_id: 1,
                 name: "2".to_string(),
+                ..Default::default()
             },
             ProcessInfo {
                 process_id: 3,
                 parent_process_id: 1,
                 name: "3".to_string(),
+                ..Default::default()
             },
         ];
         let filter = |_: &ProcessInfo| false;
@@ -205,16 +1780,19 @@ mod tests {
                 process_id: 1,
                 parent_process_id: 1,
                 name: "1".to_string(),
+                ..Default::default()
             },
             ProcessInfo {
                 process_id: 2,
                 parent_process_id: 1,
                 name: "2".to_string(),
+                ..Default::default()
             },
             ProcessInfo {
                 process_id: 3,
                 parent_process_id: 1,
                 name: "3".to_string(),
+                ..Default::default()
             },
         ];
         let filter =
@@ -230,16 +1808,19 @@ mod tests {
                 process_id: 1,
                 parent_process_id: 0,
                 name: "1".to_string(),
+                ..Default::default()
             },
             ProcessInfo {
                 process_id: 2,
                 parent_process_id: 1,
                 name: "2".to_string(),
+                ..Default::default()
             },
             ProcessInfo {
                 process_id: 3,
                 parent_process_id: 1,
                 name: "3".to_string(),
+                ..Default::default()
             },
         ];
         let map = get_process_info_map(process_infos);
@@ -253,23 +1834,370 @@ mod tests {
                 process_id: 1,
                 parent_process_id: 0,
                 name: "1".to_string(),
+                ..Default::default()
+            },
+            ProcessInfo {
+                process_id: 2,
+                parent_process_id: 1,
+                name: "2".to_string(),
+                ..Default::default()
+            },
+            ProcessInfo {
+                process_id: 3,
+                parent_process_id: 1,
+                name: "3".to_string(),
+                ..Default::default()
+            },
+        ];
+        let child_process_id_map =
+            get_child_process_id_map(&process_infos, |_: &ProcessInfo| false);
+        let config = Config::default();
+        let process_ids_to_kill =
+            get_process_ids_to_kill(1, &child_process_id_map, |_| None, &config);
+        assert_eq!(process_ids_to_kill, vec![(1, 0), (2, 1), (3, 1)]);
+    }
+
+    fn get_process_infos_to_filter() -> ProcessInfos {
+        [(1, 0), (2, 1), (3, 1), (4, 2)]
+            .into_iter()
+            .map(|(process_id, parent_process_id)| ProcessInfo {
+                process_id,
+                parent_process_id,
+                name: process_id.to_string(),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn get_process_ids_to_kill_filter_skip() {
+        let process_infos = get_process_infos_to_filter();
+        let child_process_id_map =
+            get_child_process_id_map(&process_infos, |_: &ProcessInfo| false);
+        let process_info_map = get_process_info_map(process_infos);
+        let config = Config {
+            filter: Some(Box::new(|x| {
+                if x.name() == "2" {
+                    FilterDecision::Skip
+                } else {
+                    FilterDecision::Include
+                }
+            })),
+            ..Default::default()
+        };
+        let process_ids_to_kill = get_process_ids_to_kill(
+            1,
+            &child_process_id_map,
+            |x| process_info_map.get(&x),
+            &config,
+        );
+        // 4 is a child of the skipped process 2
+        assert_eq!(process_ids_to_kill, vec![(1, 0), (3, 1), (4, 2)]);
+    }
+
+    #[test]
+    fn get_process_ids_to_kill_filter_skip_subtree() {
+        let process_infos = get_process_infos_to_filter();
+        let child_process_id_map =
+            get_child_process_id_map(&process_infos, |_: &ProcessInfo| false);
+        let process_info_map = get_process_info_map(process_infos);
+        let config = Config {
+            filter: Some(Box::new(|x| {
+                if x.process_id() == 2 {
+                    assert_eq!(x.depth(), 1);
+                    FilterDecision::SkipSubtree
+                } else {
+                    FilterDecision::Include
+                }
+            })),
+            ..Default::default()
+        };
+        let process_ids_to_kill = get_process_ids_to_kill(
+            1,
+            &child_process_id_map,
+            |x| process_info_map.get(&x),
+            &config,
+        );
+        assert_eq!(process_ids_to_kill, vec![(1, 0), (3, 1)]);
+    }
+
+    #[test]
+    fn get_kill_plan_test() {
+        let process_infos = vec![
+            ProcessInfo {
+                process_id: 1,
+                parent_process_id: 0,
+                name: "1".to_string(),
+                ..Default::default()
             },
             ProcessInfo {
                 process_id: 2,
                 parent_process_id: 1,
                 name: "2".to_string(),
+                ..Default::default()
             },
             ProcessInfo {
                 process_id: 3,
+                parent_process_id: 2,
+                name: "3".to_string(),
+                ..Default::default()
+            },
+        ];
+        let child_process_id_map =
+            get_child_process_id_map(&process_infos, |_: &ProcessInfo| false);
+        let process_info_map = get_process_info_map(process_infos);
+        let config = Config::default();
+        let kill_plan = get_kill_plan(1, &child_process_id_map, &process_info_map, &config);
+        let process_ids = kill_plan.iter().map(|x| x.process_id).collect::<Vec<_>>();
+        assert_eq!(process_ids, vec![3, 2, 1]);
+        let depths = kill_plan.iter().map(|x| x.depth).collect::<Vec<_>>();
+        assert_eq!(depths, vec![2, 1, 0]);
+        assert_eq!(kill_plan[0].reason, KillReason::Descendant);
+        assert_eq!(kill_plan[0].parent_process_id, Some(2));
+        assert_eq!(kill_plan[0].name.as_deref(), Some("3"));
+        assert_eq!(kill_plan[2].reason, KillReason::Target);
+    }
+
+    #[test]
+    fn get_kill_plan_process_group() {
+        let process_infos = vec![
+            ProcessInfo {
+                process_id: 1,
+                parent_process_id: 0,
+                name: "1".to_string(),
+                process_group_id: Some(1),
+                session_id: Some(1),
+                ..Default::default()
+            },
+            ProcessInfo {
+                process_id: 2,
                 parent_process_id: 1,
+                name: "2".to_string(),
+                process_group_id: Some(1),
+                session_id: Some(1),
+                ..Default::default()
+            },
+            // reparented to init by forking twice
+            ProcessInfo {
+                process_id: 3,
+                parent_process_id: 0,
                 name: "3".to_string(),
+                process_group_id: Some(1),
+                session_id: Some(1),
+                ..Default::default()
+            },
+            ProcessInfo {
+                process_id: 4,
+                parent_process_id: 0,
+                name: "4".to_string(),
+                process_group_id: Some(4),
+                session_id: Some(1),
+                ..Default::default()
             },
         ];
         let child_process_id_map =
             get_child_process_id_map(&process_infos, |_: &ProcessInfo| false);
+        let process_info_map = get_process_info_map(process_infos);
+        let config = Config::default();
+        let kill_plan = get_kill_plan(1, &child_process_id_map, &process_info_map, &config);
+        let process_ids = kill_plan.iter().map(|x| x.process_id).collect::<Vec<_>>();
+        assert_eq!(process_ids, vec![2, 1]);
+        let config = Config {
+            selection: Selection::ProcessGroup,
+            ..Default::default()
+        };
+        let kill_plan = get_kill_plan(1, &child_process_id_map, &process_info_map, &config);
+        let process_ids = kill_plan.iter().map(|x| x.process_id).collect::<Vec<_>>();
+        assert_eq!(process_ids, vec![3, 2, 1]);
+        assert_eq!(kill_plan[0].reason, KillReason::ProcessGroup);
+        assert_eq!(kill_plan[0].depth, 0);
+        assert_eq!(kill_plan[1].reason, KillReason::Descendant);
+        let config = Config {
+            selection: Selection::Session,
+            ..Default::default()
+        };
+        let kill_plan = get_kill_plan(1, &child_process_id_map, &process_info_map, &config);
+        let process_ids = kill_plan.iter().map(|x| x.process_id).collect::<Vec<_>>();
+        assert_eq!(process_ids, vec![4, 3, 2, 1]);
+        assert_eq!(kill_plan[0].reason, KillReason::Session);
+    }
+
+    #[test]
+    fn get_kill_plan_filter_skip_depth() {
+        // 1 -> 2 -> 4, 1 -> 3
+        let process_infos = get_process_infos_to_filter();
+        let child_process_id_map =
+            get_child_process_id_map(&process_infos, |_: &ProcessInfo| false);
+        let process_info_map = get_process_info_map(process_infos);
+        let config = Config {
+            filter: Some(Box::new(|x| {
+                if x.process_id() == 2 {
+                    FilterDecision::Skip
+                } else {
+                    FilterDecision::Include
+                }
+            })),
+            kill_order: KillOrder::RootFirst,
+            ..Default::default()
+        };
+        let kill_plan = get_kill_plan(1, &child_process_id_map, &process_info_map, &config);
+        let depths = kill_plan
+            .iter()
+            .map(|x| (x.process_id, x.depth))
+            .collect::<Vec<_>>();
+        assert_eq!(depths, vec![(1, 0), (3, 1), (4, 2)]);
+    }
+
+    #[test]
+    fn get_kill_plan_filter_process_group() {
+        // 1 -> 2 -> 4, 1 -> 3, all in the process group 1
+        let process_infos = get_process_infos_to_filter()
+            .into_iter()
+            .map(|x| ProcessInfo {
+                process_group_id: Some(1),
+                ..x
+            })
+            .chain([ProcessInfo {
+                process_id: 5,
+                parent_process_id: 0,
+                name: "5".to_string(),
+                process_group_id: Some(1),
+                ..Default::default()
+            }])
+            .collect::<ProcessInfos>();
+        let child_process_id_map =
+            get_child_process_id_map(&process_infos, |_: &ProcessInfo| false);
+        let process_info_map = get_process_info_map(process_infos);
+        for decision in [FilterDecision::Skip, FilterDecision::SkipSubtree] {
+            // skips the direct children, e.g. sidecars
+            let config = Config {
+                filter: Some(Box::new(move |x| {
+                    if x.depth() == 1 {
+                        decision
+                    } else {
+                        FilterDecision::Include
+                    }
+                })),
+                selection: Selection::ProcessGroup,
+                ..Default::default()
+            };
+            let kill_plan = get_kill_plan(1, &child_process_id_map, &process_info_map, &config);
+            let mut process_ids = kill_plan.iter().map(|x| x.process_id).collect::<Vec<_>>();
+            process_ids.sort_unstable();
+            if decision == FilterDecision::Skip {
+                assert_eq!(process_ids, vec![1, 4, 5]);
+            } else {
+                assert_eq!(process_ids, vec![1, 5]);
+            }
+        }
+    }
+
+    #[test]
+    fn get_trees_kill_plan_overlapping() {
+        // 1 -> 2 -> 4, 1 -> 3
+        let process_infos = get_process_infos_to_filter();
+        let child_process_id_map =
+            get_child_process_id_map(&process_infos, |_: &ProcessInfo| false);
+        let process_info_map = get_process_info_map(process_infos);
+        let config = Config::default();
+        let kill_plan =
+            get_trees_kill_plan(&[2, 1], &child_process_id_map, &process_info_map, &config);
+        let kill_plan = kill_plan
+            .iter()
+            .map(|(target_process_id, entry)| (*target_process_id, entry.process_id))
+            .collect::<Vec<_>>();
+        assert_eq!(kill_plan, vec![(2, 4), (2, 2), (1, 3), (1, 1)]);
+        let config = Config {
+            include_target: false,
+            ..Default::default()
+        };
+        let kill_plan =
+            get_trees_kill_plan(&[1, 2], &child_process_id_map, &process_info_map, &config);
+        let kill_plan = kill_plan
+            .iter()
+            .map(|(target_process_id, entry)| (*target_process_id, entry.process_id))
+            .collect::<Vec<_>>();
+        assert_eq!(kill_plan, vec![(2, 4), (1, 3)]);
+    }
+
+    #[test]
+    fn select_target_process_ids_nested() {
+        // 1 -> 2 -> 4, 1 -> 3
+        let process_infos = get_process_infos_to_filter();
+        let selector = ProcessSelector::new().name("2");
+        assert_eq!(
+            select_target_process_ids(
+                &selector,
+                &process_infos,
+                std::path::Path::new(CURRENT_PROC_ROOT)
+            ),
+            vec![2]
+        );
+        let selector = ProcessSelector::new();
+        assert!(select_target_process_ids(
+            &selector,
+            &process_infos,
+            std::path::Path::new(CURRENT_PROC_ROOT)
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn select_target_process_ids_current_process_lineage() {
+        // 4000 -> 4001 -> current process -> 4002, 4000 -> 4003
+        let current_process_id = std::process::id();
+        let get_process_info = |process_id, parent_process_id, name: &str| ProcessInfo {
+            process_id,
+            parent_process_id,
+            name: name.to_string(),
+            ..Default::default()
+        };
+        let process_infos = vec![
+            get_process_info(4000, 1, "sh"),
+            get_process_info(4001, 4000, "sh"),
+            get_process_info(current_process_id, 4001, "current"),
+            get_process_info(4002, current_process_id, "sh"),
+            get_process_info(4003, 4000, "sibling"),
+        ];
+        // matches the parent of the current process, which would kill the current process
+        let selector = ProcessSelector::new().name("sh");
+        assert_eq!(
+            select_target_process_ids(
+                &selector,
+                &process_infos,
+                std::path::Path::new(CURRENT_PROC_ROOT)
+            ),
+            vec![4002]
+        );
+        let selector = ProcessSelector::new().name("current");
+        assert!(select_target_process_ids(
+            &selector,
+            &process_infos,
+            std::path::Path::new(CURRENT_PROC_ROOT)
+        )
+        .is_empty());
+        let selector = ProcessSelector::new().name("sibling");
+        assert_eq!(
+            select_target_process_ids(
+                &selector,
+                &process_infos,
+                std::path::Path::new(CURRENT_PROC_ROOT)
+            ),
+            vec![4003]
+        );
+    }
+
+    #[test]
+    fn get_kill_plan_target_not_found() {
+        let child_process_id_map = ChildProcessIdMap::new();
+        let process_info_map = ProcessInfoMap::new();
         let config = Config::default();
-        let process_ids_to_kill = get_process_ids_to_kill(1, &child_process_id_map, &config);
-        assert_eq!(process_ids_to_kill, vec![1, 2, 3]);
+        let kill_plan = get_kill_plan(1, &child_process_id_map, &process_info_map, &config);
+        assert_eq!(kill_plan.len(), 1);
+        assert_eq!(kill_plan[0].process_id, 1);
+        assert_eq!(kill_plan[0].parent_process_id, None);
+        assert_eq!(kill_plan[0].name, None);
+        assert_eq!(kill_plan[0].reason, KillReason::Target);
     }
 
     #[test]
@@ -281,26 +2209,506 @@ mod tests {
                 process_id: 1,
                 parent_process_id: 0,
                 name: "1".to_string(),
+                ..Default::default()
             },
         );
         let kill_output = KillOutput::Killed { process_id: 1 };
-        let output = parse_kill_output(kill_output, &mut process_info_map).expect("output is None");
+        let output =
+            parse_kill_output(kill_output, &mut process_info_map, 0).expect("output is None");
         match output {
             Output::Killed {
                 process_id,
                 parent_process_id,
                 name,
+                ended_by_step,
+                exit_outcome,
+                round,
+                process_info,
             } => {
                 assert_eq!(process_id, 1);
                 assert_eq!(parent_process_id, 0);
                 assert_eq!(name, "1");
+                assert_eq!(ended_by_step, None);
+                assert_eq!(exit_outcome, None);
+                assert_eq!(round, 0);
+                assert_eq!(process_info.process_id, 1);
+                assert_eq!(process_info.name, "1");
             }
             Output::MaybeAlreadyTerminated {
                 process_id: _process_id,
                 source: _source,
+                ..
             } => {
                 panic!("output is MaybeAlreadyTerminated");
             }
+            Output::PidReused { .. } => {
+                panic!("output is PidReused");
+            }
+            Output::Failed { .. } => {
+                panic!("output is Failed");
+            }
+        }
+    }
+
+    fn get_kill_plan_process_ids(kill_order: KillOrder) -> ProcessIds {
+        // 1 -> 2 -> 4, 1 -> 3
+        let process_infos = get_process_infos_to_filter();
+        let child_process_id_map = get_child_process_id_map(&process_infos, |_| false);
+        let process_info_map = get_process_info_map(process_infos);
+        let config = Config {
+            kill_order,
+            ..Default::default()
+        };
+        get_kill_plan(1, &child_process_id_map, &process_info_map, &config)
+            .into_iter()
+            .map(|x| x.process_id)
+            .collect()
+    }
+
+    #[test]
+    fn get_kill_plan_kill_order() {
+        assert_eq!(
+            get_kill_plan_process_ids(KillOrder::LeavesFirst),
+            vec![4, 3, 2, 1]
+        );
+        assert_eq!(
+            get_kill_plan_process_ids(KillOrder::RootFirst),
+            vec![1, 2, 3, 4]
+        );
+        assert_eq!(
+            get_kill_plan_process_ids(KillOrder::LevelWaves {
+                delay: std::time::Duration::ZERO
+            }),
+            vec![4, 3, 2, 1]
+        );
+        assert_eq!(
+            get_kill_plan_process_ids(KillOrder::PostOrderDfs),
+            vec![4, 2, 3, 1]
+        );
+    }
+
+    #[test]
+    fn get_kill_plan_post_order_dfs_filter_skip() {
+        // 1 -> 2 -> 4, 1 -> 3
+        let process_infos = get_process_infos_to_filter();
+        let child_process_id_map = get_child_process_id_map(&process_infos, |_| false);
+        let process_info_map = get_process_info_map(process_infos);
+        let config = Config {
+            kill_order: KillOrder::PostOrderDfs,
+            filter: Some(Box::new(|x| {
+                if x.process_id() == 2 {
+                    FilterDecision::Skip
+                } else {
+                    FilterDecision::Include
+                }
+            })),
+            ..Default::default()
+        };
+        let process_ids = get_kill_plan(1, &child_process_id_map, &process_info_map, &config)
+            .into_iter()
+            .map(|x| x.process_id)
+            .collect::<Vec<_>>();
+        assert_eq!(process_ids, vec![4, 3, 1]);
+    }
+
+    #[test]
+    fn tree_killer_signal_levels_root_first() {
+        // 1 -> 2 -> 4, 1 -> 3
+        let process_infos = get_process_infos_to_filter();
+        let config = Config {
+            kill_order: KillOrder::RootFirst,
+            ..Default::default()
+        };
+        let tree_killer =
+            TreeKiller::new(&[1], &config, process_infos, 0).expect("Failed to create");
+        let levels = tree_killer
+            .signal_levels()
+            .into_iter()
+            .map(|level| level.iter().map(|x| x.process_id).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(levels, vec![vec![1], vec![2, 3], vec![4]]);
+    }
+
+    /// Spawns a process that does nothing on `SIGCONT`, to signal it instead of the current process.
+    #[cfg(unix)]
+    fn spawn_sleep() -> std::process::Child {
+        std::process::Command::new("sleep")
+            .arg("10")
+            .spawn()
+            .expect("Failed to spawn sleep")
+    }
+
+    #[cfg(all(unix, feature = "blocking"))]
+    #[test]
+    fn tree_killer_level_waves() {
+        // two processes, as if one is the parent of the other
+        let mut parent = spawn_sleep();
+        let mut child = spawn_sleep();
+        let process_id = child.id();
+        let parent_process_id = parent.id();
+        let process_infos = [(parent_process_id, 0), (process_id, parent_process_id)]
+            .into_iter()
+            .map(|(process_id, parent_process_id)| ProcessInfo {
+                process_id,
+                parent_process_id,
+                name: process_id.to_string(),
+                ..Default::default()
+            })
+            .collect();
+        let delay = std::time::Duration::from_millis(100);
+        let config = Config {
+            signal: Signal::SIGCONT,
+            kill_order: KillOrder::LevelWaves { delay },
+            ..Default::default()
+        };
+        let mut tree_killer = TreeKiller::new(&[parent_process_id], &config, process_infos, 0)
+            .expect("Failed to create");
+        let start = std::time::Instant::now();
+        tree_killer.signal_next_step().expect("Failed to signal");
+        assert!(start.elapsed() >= delay);
+        let outputs = tree_killer.into_outputs();
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(outputs[0].1.process_id(), process_id);
+        assert_eq!(outputs[1].1.process_id(), parent_process_id);
+        for x in [&mut parent, &mut child] {
+            x.kill().expect("Failed to kill");
+            x.wait().expect("Failed to wait");
+        }
+    }
+
+    #[test]
+    fn tree_killer_signal_levels() {
+        // 1 -> 2 -> 4, 1 -> 3
+        let process_infos = get_process_infos_to_filter();
+        let tree_killer =
+            TreeKiller::new(&[1], &Config::default(), process_infos, 0).expect("Failed to create");
+        let levels = tree_killer
+            .signal_levels()
+            .into_iter()
+            .map(|level| {
+                let mut process_ids = level.iter().map(|x| x.process_id).collect::<Vec<_>>();
+                process_ids.sort_unstable();
+                process_ids
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(levels, vec![vec![4], vec![2, 3], vec![1]]);
+    }
+
+    #[cfg(all(unix, feature = "tokio"))]
+    #[::tokio::test(crate = "::tokio")]
+    async fn tree_killer_signal_next_step_tokio() {
+        let mut child = spawn_sleep();
+        let process_id = child.id();
+        let process_infos = vec![ProcessInfo {
+            process_id,
+            parent_process_id: 0,
+            name: "name".to_string(),
+            ..Default::default()
+        }];
+        let config = Config {
+            // does nothing to a running process
+            signal: Signal::SIGCONT,
+            signal_concurrency: 1,
+            ..Default::default()
+        };
+        let mut tree_killer =
+            TreeKiller::new(&[process_id], &config, process_infos, 0).expect("Failed to create");
+        let timeout = self::tokio::signal_next_step(&mut tree_killer, &config)
+            .await
+            .expect("Failed to signal");
+        assert_eq!(timeout, None);
+        let outputs = tree_killer.into_outputs();
+        assert_eq!(outputs.len(), 1);
+        assert!(matches!(outputs[0].1, Output::Killed { .. }));
+        child.kill().expect("Failed to kill");
+        child.wait().expect("Failed to wait");
+    }
+
+    #[cfg(all(unix, feature = "blocking"))]
+    #[test]
+    fn tree_killer_pid_reused() {
+        let mut child = spawn_sleep();
+        let process_id = child.id();
+        let process_infos = vec![ProcessInfo {
+            process_id,
+            parent_process_id: 0,
+            name: "name".to_string(),
+            // never matches the start time of the process
+            start_time: Some(u64::MAX),
+            ..Default::default()
+        }];
+        let config = Config {
+            // does nothing to a running process even if it is sent
+            signal: Signal::SIGCONT,
+            ..Default::default()
+        };
+        let mut tree_killer =
+            TreeKiller::new(&[process_id], &config, process_infos, 0).expect("Failed to create");
+        let timeout = tree_killer.signal_next_step().expect("Failed to signal");
+        assert_eq!(timeout, None);
+        let outputs = tree_killer.into_outputs();
+        assert_eq!(outputs.len(), 1);
+        assert_eq!(outputs[0].0, process_id);
+        match &outputs[0].1 {
+            Output::PidReused {
+                process_id: x,
+                parent_process_id,
+                name,
+                ..
+            } => {
+                assert_eq!(*x, process_id);
+                assert_eq!(*parent_process_id, 0);
+                assert_eq!(name, "name");
+            }
+            _ => {
+                panic!("output is not PidReused");
+            }
+        }
+        child.kill().expect("Failed to kill");
+        child.wait().expect("Failed to wait");
+    }
+
+    #[cfg(all(unix, feature = "tokio"))]
+    #[::tokio::test(crate = "::tokio")]
+    async fn tree_killer_signal_next_step_tokio_error_policy() {
+        let mut children = [spawn_sleep(), spawn_sleep(), spawn_sleep()];
+        let root_process_id = children[0].id();
+        // fails to be cast to i32, after the other processes of the level
+        let failing_process_id = u32::MAX;
+        let process_infos = [
+            (root_process_id, 0),
+            (children[1].id(), root_process_id),
+            (children[2].id(), root_process_id),
+            (failing_process_id, root_process_id),
+        ]
+        .into_iter()
+        .map(|(process_id, parent_process_id)| ProcessInfo {
+            process_id,
+            parent_process_id,
+            name: process_id.to_string(),
+            ..Default::default()
+        })
+        .collect::<ProcessInfos>();
+        for error_policy in [ErrorPolicy::FailFast, ErrorPolicy::ContinueAndCollect] {
+            let config = Config {
+                // does nothing to a running process
+                signal: Signal::SIGCONT,
+                kill_order: KillOrder::RootFirst,
+                // one process per task
+                signal_concurrency: 3,
+                error_policy,
+                ..Default::default()
+            };
+            let mut tree_killer =
+                TreeKiller::new(&[root_process_id], &config, process_infos.clone(), 0)
+                    .expect("Failed to create");
+            let result = self::tokio::signal_next_step(&mut tree_killer, &config).await;
+            if error_policy == ErrorPolicy::FailFast {
+                assert!(matches!(
+                    result,
+                    Err(crate::core::Error::InvalidCast { .. })
+                ));
+                continue;
+            }
+            result.expect("Failed to signal");
+            let outputs = tree_killer.into_outputs();
+            assert_eq!(outputs.len(), 4);
+            for (_, output) in outputs {
+                if output.process_id() == failing_process_id {
+                    assert!(matches!(output, Output::Failed { .. }));
+                } else {
+                    assert!(matches!(output, Output::Killed { .. }));
+                }
+            }
+        }
+        for child in &mut children {
+            child.kill().expect("Failed to kill");
+            child.wait().expect("Failed to wait");
+        }
+    }
+
+    /// Fails to signal every process, e.g. with `EPERM`.
+    struct FailingKillable {}
+
+    impl Killable for FailingKillable {
+        fn kill(&self, _process_id: ProcessId) -> Result<KillOutput> {
+            Err(std::io::Error::from(std::io::ErrorKind::PermissionDenied).into())
+        }
+    }
+
+    #[test]
+    fn signal_process_error_policy() {
+        let target = SignalTarget {
+            entry_index: 0,
+            process_id: 777,
+            start_time: None,
+        };
+        let proc_root = std::path::Path::new(CURRENT_PROC_ROOT);
+        let result = signal_process(
+            &FailingKillable {},
+            proc_root,
+            target,
+            ErrorPolicy::FailFast,
+        );
+        assert!(matches!(result, Err(crate::core::Error::Io(_))));
+        let result = signal_process(
+            &FailingKillable {},
+            proc_root,
+            target,
+            ErrorPolicy::ContinueAndCollect,
+        );
+        assert!(matches!(
+            result,
+            Ok(Some(KillOutput::Failed {
+                process_id: 777,
+                source: crate::core::Error::Io(_),
+            }))
+        ));
+    }
+
+    #[test]
+    fn tree_killer_failed() {
+        // not running, so that no signal is sent to a real process
+        let parent_process_id = get_available_max_process_id() - 2;
+        let child_process_id = parent_process_id + 1;
+        let process_infos = vec![
+            ProcessInfo {
+                process_id: parent_process_id,
+                parent_process_id: 1,
+                name: "parent".to_string(),
+                ..Default::default()
+            },
+            ProcessInfo {
+                process_id: child_process_id,
+                parent_process_id,
+                name: "child".to_string(),
+                ..Default::default()
+            },
+        ];
+        let config = Config {
+            escalation: vec![
+                EscalationStep {
+                    signal: Signal::SIGTERM,
+                    timeout: std::time::Duration::ZERO,
+                },
+                EscalationStep {
+                    signal: Signal::SIGKILL,
+                    timeout: std::time::Duration::ZERO,
+                },
+            ],
+            error_policy: ErrorPolicy::ContinueAndCollect,
+            ..Default::default()
+        };
+        let mut tree_killer = TreeKiller::new(&[parent_process_id], &config, process_infos, 0)
+            .expect("Failed to create");
+        let entry_index = |process_id: ProcessId, tree_killer: &TreeKiller| {
+            tree_killer
+                .entries
+                .iter()
+                .position(|x| x.process_id == process_id)
+                .expect("Failed to find entry")
+        };
+        let failed = |process_id| KillOutput::Failed {
+            process_id,
+            source: std::io::Error::from(std::io::ErrorKind::PermissionDenied).into(),
+        };
+        // the child fails at the first step
+        let step = tree_killer.begin_next_step().expect("No step");
+        let kill_outputs = vec![
+            (
+                entry_index(child_process_id, &tree_killer),
+                Some(failed(child_process_id)),
+            ),
+            (
+                entry_index(parent_process_id, &tree_killer),
+                Some(KillOutput::Killed {
+                    process_id: parent_process_id,
+                }),
+            ),
+        ];
+        tree_killer.end_step(&step, kill_outputs);
+        // the parent fails at the second step, and the child is not signalled again
+        let step = tree_killer.begin_next_step().expect("No step");
+        let signal_levels = tree_killer.signal_levels();
+        assert_eq!(signal_levels.len(), 1);
+        assert_eq!(signal_levels[0].len(), 1);
+        assert_eq!(signal_levels[0][0].process_id, parent_process_id);
+        let kill_outputs = vec![(
+            entry_index(parent_process_id, &tree_killer),
+            Some(failed(parent_process_id)),
+        )];
+        tree_killer.end_step(&step, kill_outputs);
+        assert!(tree_killer.begin_next_step().is_none());
+        let outputs = tree_killer.into_outputs();
+        assert_eq!(outputs.len(), 2);
+        for (target_process_id, output) in outputs {
+            assert_eq!(target_process_id, parent_process_id);
+            let Output::Failed {
+                process_id,
+                process_info,
+                ..
+            } = output
+            else {
+                panic!("output is not Failed");
+            };
+            let name = process_info.expect("process info is None").name;
+            match process_id {
+                x if x == parent_process_id => assert_eq!(name, "parent"),
+                x if x == child_process_id => assert_eq!(name, "child"),
+                _ => panic!("Unexpected process id: {process_id}"),
+            }
         }
     }
+
+    #[test]
+    fn rounds_without_repeat() {
+        let config = Config::default();
+        let mut rounds = Rounds::new(&[1], &config);
+        assert!(rounds.has_next());
+        let tree_killer = rounds
+            .start_next(&config, ProcessInfos::new())
+            .expect("Failed to start");
+        assert!(tree_killer.is_some());
+        assert!(!rounds.has_next());
+    }
+
+    #[test]
+    fn rounds_repeat_until_no_live_process() {
+        let config = Config {
+            repeat: Some(crate::RepeatLimit {
+                max_rounds: 3,
+                deadline: None,
+            }),
+            ..Default::default()
+        };
+        let mut rounds = Rounds::new(&[get_available_max_process_id()], &config);
+        assert!(rounds.has_next());
+        let tree_killer = rounds
+            .start_next(&config, ProcessInfos::new())
+            .expect("Failed to start");
+        assert!(tree_killer.is_some());
+        assert!(rounds.has_next());
+        // the target process is not found, so there is nothing to kill
+        let tree_killer = rounds
+            .start_next(&config, ProcessInfos::new())
+            .expect("Failed to start");
+        assert!(tree_killer.is_none());
+    }
+
+    #[test]
+    fn rounds_repeat_deadline() {
+        let config = Config {
+            repeat: Some(crate::RepeatLimit {
+                max_rounds: 3,
+                deadline: Some(std::time::Duration::ZERO),
+            }),
+            ..Default::default()
+        };
+        let mut rounds = Rounds::new(&[1], &config);
+        assert!(rounds.has_next());
+        let _ = rounds
+            .start_next(&config, ProcessInfos::new())
+            .expect("Failed to start");
+        assert!(!rounds.has_next());
+    }
 }
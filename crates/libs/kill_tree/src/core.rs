@@ -14,6 +14,10 @@ pub enum Error {
         reason: String,
         source: Option<std::num::ParseIntError>,
     },
+    InvalidSignal {
+        signal: String,
+        reason: String,
+    },
     Io(std::io::Error),
     #[cfg(windows)]
     Windows(windows::core::Error),
@@ -39,6 +43,9 @@ impl std::fmt::Display for Error {
                 f,
                 "Invalid proc entry. Process id: {process_id}. Path: {path}. Reason: {reason}. Source: {source:?}"
             ),
+            Error::InvalidSignal { signal, reason } => {
+                write!(f, "Invalid signal: {signal}. Reason: {reason}")
+            }
             Error::Io(e) => write!(f, "I/O error: {e}"),
             #[cfg(windows)]
             Error::Windows(e) => write!(f, "Windows error: {e}"),
@@ -72,20 +79,159 @@ impl From<nix::Error> for Error {
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// A signal to send to the processes.  
+/// It is parsed from a name with or without the `SIG` prefix, e.g. `SIGTERM` or `TERM`, or a number, e.g. `15`.  
+/// On Linux, real-time signals are also parsed, e.g. `SIGRTMIN+3` or `SIGRTMAX-1`.  
+///
+/// # Platform-specifics
+///
+/// ## Windows
+/// Processes are always terminated by `TerminateProcess`, whatever the signal is.  
+/// The signal is validated with the names and numbers of Linux, but it is never sent.  
+///
+/// # Examples
+///
+/// ```
+/// use kill_tree::{Result, Signal};
+///
+/// fn main() -> Result<()> {
+///     assert_eq!("SIGTERM".parse::<Signal>()?, Signal::SIGTERM);
+///     assert_eq!("TERM".parse::<Signal>()?, Signal::SIGTERM);
+///     assert_eq!("15".parse::<Signal>()?, Signal::SIGTERM);
+///     assert!("SIGTRM".parse::<Signal>().is_err());
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Signal(i32);
+
+impl Signal {
+    pub const SIGHUP: Self = Self(1);
+    pub const SIGINT: Self = Self(2);
+    pub const SIGQUIT: Self = Self(3);
+    pub const SIGKILL: Self = Self(9);
+    pub const SIGTERM: Self = Self(15);
+    #[cfg(unix)]
+    pub const SIGSTOP: Self = Self(nix::libc::SIGSTOP);
+    #[cfg(windows)]
+    pub const SIGSTOP: Self = Self(19);
+    #[cfg(unix)]
+    pub const SIGCONT: Self = Self(nix::libc::SIGCONT);
+    #[cfg(windows)]
+    pub const SIGCONT: Self = Self(18);
+
+    /// Number of the signal on the current platform.
+    #[must_use]
+    pub fn number(self) -> i32 {
+        self.0
+    }
+}
+
+impl Default for Signal {
+    fn default() -> Self {
+        Self::SIGTERM
+    }
+}
+
+impl std::str::FromStr for Signal {
+    type Err = Error;
+
+    fn from_str(signal: &str) -> Result<Self> {
+        let number = if let Ok(number) = signal.parse::<i32>() {
+            number
+        } else {
+            let name = signal.to_ascii_uppercase();
+            let name = name.strip_prefix("SIG").unwrap_or(&name);
+            crate::common::parse_signal_name(name).ok_or_else(|| Error::InvalidSignal {
+                signal: signal.to_string(),
+                reason: "Unknown signal name".into(),
+            })?
+        };
+        if crate::common::get_signal_name(number).is_none() {
+            return Err(Error::InvalidSignal {
+                signal: signal.to_string(),
+                reason: "Unknown signal number".into(),
+            });
+        }
+        Ok(Self(number))
+    }
+}
+
+impl std::fmt::Display for Signal {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match crate::common::get_signal_name(self.0) {
+            Some(name) => write!(f, "{name}"),
+            None => write!(f, "{}", self.0),
+        }
+    }
+}
+
 pub type ProcessId = u32;
 pub type ParentProcessId = ProcessId;
 
-#[derive(Debug)]
-pub(crate) struct ProcessInfo {
-    pub(crate) process_id: ProcessId,
-    pub(crate) parent_process_id: ParentProcessId,
-    pub(crate) name: String,
+/// Details of a process, queried before any signal is sent.  
+/// Fields may be added, so construct it with `ProcessInfo::new` outside this crate.  
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ProcessInfo {
+    pub process_id: ProcessId,
+    pub parent_process_id: ParentProcessId,
+    /// Name of the process, e.g. `comm` on Linux, which is truncated to 15 bytes.
+    pub name: String,
+    /// `None` if the platform does not provide it.
+    pub process_group_id: Option<ProcessId>,
+    /// `None` if the platform does not provide it.
+    pub session_id: Option<ProcessId>,
+    /// Start time of the process, to detect that the process id has been reused.  
+    /// Clock ticks since boot on Linux, and microseconds since the epoch on Macos.  
+    /// `None` if the platform does not provide it.  
+    pub start_time: Option<u64>,
+    /// Real user id, or the effective one on Linux, where it is the owner of `/proc/<pid>`.  
+    /// `None` if the platform does not provide it.  
+    pub user_id: Option<u32>,
+    /// Real group id, or the effective one on Linux, where it is the owner of `/proc/<pid>`.  
+    /// `None` if the platform does not provide it.  
+    pub group_id: Option<u32>,
+    /// State of the process, e.g. `R` running, `S` sleeping, `T` stopped or `Z` zombie.  
+    /// `None` if the platform does not provide it.  
+    pub state: Option<char>,
+    /// Command line arguments, queried only for the processes to kill.  
+    /// `None` if it is not allowed to inquire, or the platform does not provide it.  
+    pub cmdline: Option<Vec<String>>,
+    /// Path of the executable, queried only for the processes to kill.  
+    /// `None` if it is not allowed to inquire, or the platform does not provide it.  
+    pub exe: Option<std::path::PathBuf>,
+    /// Process ids in each nested pid namespace, from `NSpid` of status on Linux.  
+    /// The first is the one in the pid namespace of the procfs, and the last is the one in the namespace of the process.  
+    /// Queried only for the processes to kill, or for every process when `Config::proc_root` is of another pid namespace.  
+    /// `None` if it is not allowed to inquire, or the platform does not provide it.  
+    pub namespace_process_ids: Option<Vec<ProcessId>>,
+    /// Process group ids in each nested pid namespace, from `NSpgid` of status on Linux, in the same order.  
+    /// `None` if it is not allowed to inquire, or the platform does not provide it.  
+    pub namespace_process_group_ids: Option<Vec<ProcessId>>,
+}
+
+impl ProcessInfo {
+    /// Creates a process info with the other fields unknown, e.g. for `StaticProcessTable`.
+    #[must_use]
+    pub fn new(
+        process_id: ProcessId,
+        parent_process_id: ParentProcessId,
+        name: impl Into<String>,
+    ) -> Self {
+        Self {
+            process_id,
+            parent_process_id,
+            name: name.into(),
+            ..Default::default()
+        }
+    }
 }
 
 pub(crate) type ChildProcessId = ProcessId;
 pub(crate) type ChildProcessIds = Vec<ChildProcessId>;
 pub(crate) type ProcessIds = Vec<ProcessId>;
-pub(crate) type ProcessInfos = Vec<ProcessInfo>;
+pub type ProcessInfos = Vec<ProcessInfo>;
 pub(crate) type ChildProcessIdMap = std::collections::HashMap<ProcessId, ChildProcessIds>;
 pub(crate) type ProcessInfoMap = std::collections::HashMap<ProcessId, ProcessInfo>;
 pub(crate) type ChildProcessIdMapFilter = fn(&ProcessInfo) -> bool;
@@ -95,7 +241,7 @@ pub(crate) trait Killable {
 }
 
 pub(crate) trait KillableBuildable {
-    fn new_killable(&self, config: &Config) -> Result<impl Killable>;
+    fn new_killable(&self, signal: Signal) -> Result<impl Killable>;
 }
 
 #[derive(Debug)]
@@ -107,6 +253,11 @@ pub(crate) enum KillOutput {
         process_id: ProcessId,
         source: Error,
     },
+    /// Failed to signal the process, reported instead of the error by `ErrorPolicy::ContinueAndCollect`.
+    Failed {
+        process_id: ProcessId,
+        source: Error,
+    },
 }
 
 #[derive(Debug)]
@@ -115,45 +266,900 @@ pub enum Output {
         process_id: ProcessId,
         parent_process_id: ParentProcessId,
         name: String,
+        /// Index of the `Config::escalation` step after which the process was observed to have exited.  
+        /// `None` if escalation is not used, or if the process was still alive after the last step.  
+        ended_by_step: Option<usize>,
+        /// Whether the process has actually exited.  
+        /// `None` if `Config::wait_for_exit` is not set.  
+        exit_outcome: Option<ExitOutcome>,
+        /// Index of the round in which the process was found. See `Config::repeat`.  
+        round: usize,
+        /// Details of the process, queried before the signal was sent.
+        process_info: ProcessInfo,
     },
     MaybeAlreadyTerminated {
         process_id: ProcessId,
         source: Error,
+        round: usize,
+        /// `None` if the process was not found when the process infos were queried.
+        process_info: Option<ProcessInfo>,
+    },
+    /// The process id has been reused by another process since the process infos were queried.  
+    /// The process that was queried has already terminated, so no signal was sent.  
+    /// `parent_process_id` and `name` are of the process that was queried.  
+    PidReused {
+        process_id: ProcessId,
+        parent_process_id: ParentProcessId,
+        name: String,
+        round: usize,
+        process_info: ProcessInfo,
+    },
+    /// Failed to send the signal to the process, e.g. `EPERM` for a process owned by another user.  
+    /// Returned instead of the error with `ErrorPolicy::ContinueAndCollect`, so the other processes are still killed.  
+    /// No more signal is sent to the process.  
+    Failed {
+        process_id: ProcessId,
+        source: Error,
+        round: usize,
+        /// `None` if the process was not found when the process infos were queried.
+        process_info: Option<ProcessInfo>,
     },
 }
 
+impl Output {
+    #[must_use]
+    pub fn process_id(&self) -> ProcessId {
+        match self {
+            Self::Killed { process_id, .. }
+            | Self::MaybeAlreadyTerminated { process_id, .. }
+            | Self::PidReused { process_id, .. }
+            | Self::Failed { process_id, .. } => *process_id,
+        }
+    }
+
+    /// `None` if the process was not found when the process infos were queried.
+    #[must_use]
+    pub fn process_info(&self) -> Option<&ProcessInfo> {
+        match self {
+            Self::Killed { process_info, .. } | Self::PidReused { process_info, .. } => {
+                Some(process_info)
+            }
+            Self::MaybeAlreadyTerminated { process_info, .. }
+            | Self::Failed { process_info, .. } => process_info.as_ref(),
+        }
+    }
+}
+
 pub type Outputs = Vec<Output>;
 
+/// A process in a `KillTreeReport`, with its child processes.
 #[derive(Debug)]
+pub struct KillTreeNode {
+    pub output: Output,
+    /// Distance from the root of the report. The root is `0`.
+    pub depth: usize,
+    pub children: Vec<KillTreeNode>,
+}
+
+/// Numbers of the processes in a `KillTreeReport` by outcome.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KillTreeSummary {
+    pub total: usize,
+    pub killed: usize,
+    pub maybe_already_terminated: usize,
+    pub pid_reused: usize,
+    /// Processes that could not be signalled. See `ErrorPolicy::ContinueAndCollect`.
+    pub failed: usize,
+    /// Killed processes observed to have exited. See `Config::wait_for_exit`.
+    pub exited: usize,
+    /// Killed processes observed to be zombies. See `Config::wait_for_exit`.
+    pub zombie: usize,
+    /// Killed processes still alive when waiting timed out. See `Config::wait_for_exit`.
+    pub still_alive: usize,
+}
+
+/// Outputs arranged as the process tree, instead of the order they were killed.  
+/// The parent of each process is taken from the process infos queried before killing, as the tree to kill was built.  
+/// A process whose parent process is not in the outputs is a root,
+/// e.g. the target process, or a process included only by process group or session.  
+///
+/// # Examples
+///
+/// ```
+/// use kill_tree::{blocking::kill_tree, get_available_max_process_id, KillTreeReport, Result};
+///
+/// fn main() -> Result<()> {
+///     let outputs = kill_tree(get_available_max_process_id())?; // Replace with your target process ID.
+///     let report = KillTreeReport::new(outputs);
+///     for node in report.pre_order() {
+///         println!("{}{}", "  ".repeat(node.depth), node.output.process_id());
+///     }
+///     println!("{:?}", report.summary());
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct KillTreeReport {
+    pub roots: Vec<KillTreeNode>,
+}
+
+impl KillTreeReport {
+    /// Arranges the outputs as the process tree.  
+    /// Children are in the order they were found, from the root down.  
+    #[must_use]
+    pub fn new(outputs: Outputs) -> Self {
+        // from the root down, the reverse of the order they were killed
+        let mut outputs = outputs.into_iter().rev().map(Some).collect::<Vec<_>>();
+        let mut index_map = std::collections::HashMap::new();
+        for (index, output) in outputs.iter().enumerate() {
+            if let Some(output) = output {
+                index_map.entry(output.process_id()).or_insert(index);
+            }
+        }
+        let mut children_indices = vec![Vec::new(); outputs.len()];
+        let mut root_indices = Vec::new();
+        for (index, output) in outputs.iter().enumerate() {
+            let parent_index = output
+                .as_ref()
+                .and_then(Output::process_info)
+                .filter(|x| x.parent_process_id != x.process_id)
+                .and_then(|x| index_map.get(&x.parent_process_id))
+                .copied()
+                .filter(|x| *x != index);
+            match parent_index {
+                Some(parent_index) => children_indices[parent_index].push(index),
+                None => root_indices.push(index),
+            }
+        }
+        // visits from the roots to find depths, so that a cycle of parents is never visited
+        let mut depths = vec![None; outputs.len()];
+        let mut order = Vec::new();
+        let mut queue = root_indices
+            .iter()
+            .map(|x| (*x, 0))
+            .collect::<std::collections::VecDeque<_>>();
+        loop {
+            while let Some((index, depth)) = queue.pop_front() {
+                if depths[index].is_some() {
+                    continue;
+                }
+                depths[index] = Some(depth);
+                order.push(index);
+                queue.extend(children_indices[index].iter().map(|x| (*x, depth + 1)));
+            }
+            // processes in a cycle of parents, e.g. by a reused process id, become roots
+            let Some(index) = depths.iter().position(Option::is_none) else {
+                break;
+            };
+            root_indices.push(index);
+            queue.push_back((index, 0));
+        }
+        // builds children before their parents
+        let mut nodes = std::iter::repeat_with(|| None)
+            .take(outputs.len())
+            .collect::<Vec<Option<KillTreeNode>>>();
+        for &index in order.iter().rev() {
+            let Some(output) = outputs[index].take() else {
+                continue;
+            };
+            let children = children_indices[index]
+                .iter()
+                .filter_map(|x| nodes[*x].take())
+                .collect();
+            nodes[index] = Some(KillTreeNode {
+                output,
+                depth: depths[index].unwrap_or_default(),
+                children,
+            });
+        }
+        Self {
+            roots: root_indices
+                .iter()
+                .filter_map(|x| nodes[*x].take())
+                .collect(),
+        }
+    }
+
+    /// Iterates the nodes, each parent before its children.
+    #[must_use]
+    pub fn pre_order(&self) -> KillTreePreOrder<'_> {
+        KillTreePreOrder {
+            stack: self.roots.iter().rev().collect(),
+        }
+    }
+
+    /// Iterates the nodes, each parent after its children.
+    #[must_use]
+    pub fn post_order(&self) -> KillTreePostOrder<'_> {
+        KillTreePostOrder {
+            stack: self.roots.iter().rev().map(|x| (x, false)).collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn summary(&self) -> KillTreeSummary {
+        let mut summary = KillTreeSummary::default();
+        for node in self.pre_order() {
+            summary.total += 1;
+            match &node.output {
+                Output::Killed { exit_outcome, .. } => {
+                    summary.killed += 1;
+                    match exit_outcome {
+                        Some(ExitOutcome::Exited) => summary.exited += 1,
+                        Some(ExitOutcome::Zombie) => summary.zombie += 1,
+                        Some(ExitOutcome::StillAlive) => summary.still_alive += 1,
+                        None => {}
+                    }
+                }
+                Output::MaybeAlreadyTerminated { .. } => summary.maybe_already_terminated += 1,
+                Output::PidReused { .. } => summary.pid_reused += 1,
+                Output::Failed { .. } => summary.failed += 1,
+            }
+        }
+        summary
+    }
+}
+
+impl From<Outputs> for KillTreeReport {
+    fn from(outputs: Outputs) -> Self {
+        Self::new(outputs)
+    }
+}
+
+/// Iterator of `KillTreeReport::pre_order`.
+#[derive(Debug)]
+pub struct KillTreePreOrder<'a> {
+    stack: Vec<&'a KillTreeNode>,
+}
+
+impl<'a> Iterator for KillTreePreOrder<'a> {
+    type Item = &'a KillTreeNode;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.stack.extend(node.children.iter().rev());
+        Some(node)
+    }
+}
+
+/// Iterator of `KillTreeReport::post_order`.
+#[derive(Debug)]
+pub struct KillTreePostOrder<'a> {
+    /// Nodes with whether their children have been pushed.
+    stack: Vec<(&'a KillTreeNode, bool)>,
+}
+
+impl<'a> Iterator for KillTreePostOrder<'a> {
+    type Item = &'a KillTreeNode;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node, visited) = self.stack.pop()?;
+            if visited {
+                return Some(node);
+            }
+            self.stack.push((node, true));
+            self.stack
+                .extend(node.children.iter().rev().map(|x| (x, false)));
+        }
+    }
+}
+
+/// Outputs of a target process of `kill_trees`.
+#[derive(Debug)]
+pub struct TreeOutputs {
+    /// The target process.
+    pub process_id: ProcessId,
+    /// Outputs of the processes in the tree of the target process.  
+    /// A process in the trees of several target processes is included only in the tree of the nearest one.  
+    pub outputs: Outputs,
+}
+
+/// Whether a killed process has actually exited.  
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitOutcome {
+    /// The process no longer exists.
+    Exited,
+    /// The process has terminated, but has not been reaped by its parent process yet.  
+    /// It no longer holds resources such as ports or files.  
+    Zombie,
+    /// The process was still alive when the timeout elapsed.
+    StillAlive,
+}
+
+/// Exit of a killed process observed by `tokio::wait_tree_exit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProcessExit {
+    pub process_id: ProcessId,
+    /// `Exited` or `Zombie` if the process has terminated, `StillAlive` if the timeout elapsed first.
+    pub exit_outcome: ExitOutcome,
+}
+
+/// Why a process is included in a `KillPlan`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KillReason {
+    /// The process is the target process itself.
+    Target,
+    /// The process is a child process of the target process, directly or recursively.
+    Descendant,
+    /// The process is in the process group of the target process. See `Selection::ProcessGroup`.
+    ProcessGroup,
+    /// The process is in the session of the target process. See `Selection::Session`.
+    Session,
+}
+
+/// A process that would be killed, as reported by `plan_kill_tree`.
+#[derive(Debug)]
+pub struct KillPlanEntry {
+    pub process_id: ProcessId,
+    /// `None` if the process was not found when the process infos were queried.
+    pub parent_process_id: Option<ParentProcessId>,
+    /// `None` if the process was not found when the process infos were queried.
+    pub name: Option<String>,
+    /// Distance from the target process. The target process is `0`.  
+    /// Also `0` for the processes included only by process group or session.  
+    pub depth: usize,
+    pub reason: KillReason,
+}
+
+/// Processes in the order they would be killed.
+pub type KillPlan = Vec<KillPlanEntry>;
+
+/// A step of graceful escalation.  
+/// The `signal` is sent to the processes that are still alive, then waits up to `timeout` for them to exit.  
+#[derive(Debug, Clone)]
+pub struct EscalationStep {
+    pub signal: Signal,
+    pub timeout: std::time::Duration,
+}
+
+/// Which processes are killed in addition to the target process.  
+/// The current process is never included by process group or session.  
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Selection {
+    /// The child processes of the target process, directly or recursively.
+    #[default]
+    Descendants,
+    /// The descendants, and the processes in the process group of the target process.  
+    /// This includes the processes that have been reparented to init by forking twice.  
+    ProcessGroup,
+    /// The descendants, and the processes in the session of the target process.
+    Session,
+}
+
+/// Order in which the processes of a tree are killed.  
+/// The processes included only by process group or session are killed first, except by `RootFirst`.  
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KillOrder {
+    /// Children before their parents, from the deepest processes to the target process.
+    #[default]
+    LeavesFirst,
+    /// Parents before their children, from the target process to the deepest processes.  
+    /// e.g. so that a supervisor does not restart the workers that have just been killed.  
+    RootFirst,
+    /// Same as `LeavesFirst`, but waits for `delay` before signalling the processes of the next depth.  
+    LevelWaves { delay: std::time::Duration },
+    /// Depth-first post-order, so that every subtree is killed, children first, before the next sibling subtree.
+    PostOrderDfs,
+}
+
+/// What to do when a process cannot be signalled, e.g. with `EPERM`. See `Config::error_policy`.  
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ErrorPolicy {
+    /// Returns the error at once. The processes not signalled yet are left running, and the outputs so far are dropped.
+    #[default]
+    FailFast,
+    /// Reports the error as `Output::Failed`, and keeps killing the other processes.
+    ContinueAndCollect,
+}
+
+/// Limits of repeating rounds of querying and killing. See `Config::repeat`.  
+#[derive(Debug, Clone)]
+pub struct RepeatLimit {
+    /// Max number of rounds, including the first round.
+    pub max_rounds: usize,
+    /// No new round is started after this time has elapsed since the first round started.
+    pub deadline: Option<std::time::Duration>,
+}
+
+/// Decision of `Config::filter` for a process.  
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterDecision {
+    /// The process is killed, and its child processes are visited.
+    Include,
+    /// The process is not killed, but its child processes are visited.
+    Skip,
+    /// Neither the process nor its child processes are killed.
+    SkipSubtree,
+}
+
+/// A process given to `Config::filter`.
+pub struct ProcessView<'a> {
+    pub(crate) process_info: &'a ProcessInfo,
+    pub(crate) depth: usize,
+    /// See `Config::proc_root`.
+    pub(crate) proc_root: &'a std::path::Path,
+}
+
+impl ProcessView<'_> {
+    #[must_use]
+    pub fn process_id(&self) -> ProcessId {
+        self.process_info.process_id
+    }
+
+    #[must_use]
+    pub fn parent_process_id(&self) -> ParentProcessId {
+        self.process_info.parent_process_id
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.process_info.name
+    }
+
+    /// Real user id, or the effective one on Linux.  
+    /// `None` on Windows.  
+    #[must_use]
+    pub fn user_id(&self) -> Option<u32> {
+        self.process_info.user_id
+    }
+
+    /// Distance from the target process. The target process is `0`.  
+    /// Also `0` for the processes included only by process group or session.  
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Command line arguments, including the program.  
+    /// It is read from the system on each call, because it is not queried in advance.  
+    /// On Linux, it is read from `Config::proc_root`.  
+    /// `None` if the process has terminated, it is not allowed to inquire, or on Windows.  
+    #[must_use]
+    pub fn cmdline(&self) -> Option<Vec<String>> {
+        crate::common::get_cmdline(self.proc_root, self.process_info.process_id)
+    }
+}
+
+impl std::fmt::Debug for ProcessView<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("ProcessView")
+            .field("process_id", &self.process_id())
+            .field("parent_process_id", &self.parent_process_id())
+            .field("name", &self.name())
+            .field("user_id", &self.user_id())
+            .field("depth", &self.depth())
+            .finish()
+    }
+}
+
+/// Function to decide whether each process is killed. See `Config::filter`.  
+pub type ProcessFilter = Box<dyn Fn(&ProcessView) -> FilterDecision + Send + Sync>;
+
+/// Function to match the command line of each process. See `ProcessSelector::cmdline`.  
+pub type CmdlineMatcher = Box<dyn Fn(&str) -> bool + Send + Sync>;
+
+/// Selects the target processes by name or command line, like `pkill`.  
+/// A process matches if it matches every criterion set. No process matches if no criterion is set.  
+/// The command line is not available on Windows, so no process matches `cmdline` there.  
+///
+/// # Examples
+///
+/// ```
+/// use kill_tree::ProcessSelector;
+///
+/// let selector = ProcessSelector::new()
+///     .name("node")
+///     .cmdline(|cmdline| cmdline.contains("server.js"));
+/// ```
+#[derive(Default)]
+pub struct ProcessSelector {
+    name: Option<String>,
+    cmdline: Option<CmdlineMatcher>,
+}
+
+impl ProcessSelector {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Matches the processes named `name`.  
+    /// The name of a process can be truncated, e.g. to 15 bytes on Linux,
+    /// so the file name of the first argument of the command line is also compared.  
+    #[must_use]
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Matches the processes whose full command line satisfies `matcher`, e.g. a regular expression.  
+    /// The arguments are joined with spaces.  
+    #[must_use]
+    pub fn cmdline(mut self, matcher: impl Fn(&str) -> bool + Send + Sync + 'static) -> Self {
+        self.cmdline = Some(Box::new(matcher));
+        self
+    }
+
+    /// Returns whether the process matches every criterion set.  
+    /// The command line is read only if the name can be truncated, or a command line matcher is set after the name matched.  
+    #[must_use]
+    pub fn matches(&self, process: &ProcessView) -> bool {
+        if self.name.is_none() && self.cmdline.is_none() {
+            return false;
+        }
+        let cmdline = std::cell::OnceCell::new();
+        let get_cmdline = || cmdline.get_or_init(|| process.cmdline());
+        if let Some(name) = &self.name {
+            // the name of the process is a prefix of the name if it is truncated
+            let is_truncated = !process.name().is_empty()
+                && process.name().len() < name.len()
+                && name.starts_with(process.name());
+            let matched = process.name() == name
+                || (is_truncated
+                    && get_cmdline()
+                        .as_ref()
+                        .and_then(|x| x.first())
+                        .and_then(|x| std::path::Path::new(x).file_name())
+                        .and_then(|x| x.to_str())
+                        == Some(name.as_str()));
+            if !matched {
+                return false;
+            }
+        }
+        if let Some(matcher) = &self.cmdline {
+            if !get_cmdline()
+                .as_ref()
+                .is_some_and(|x| matcher(&x.join(" ")))
+            {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl std::fmt::Debug for ProcessSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("ProcessSelector")
+            .field("name", &self.name)
+            .field(
+                "cmdline",
+                &self.cmdline.as_ref().map(|_| "Fn(&str) -> bool"),
+            )
+            .finish()
+    }
+}
+
+/// Settings to kill by cgroup v2. See `Config::cgroup`.  
+#[derive(Debug, Clone)]
+pub struct CgroupConfig {
+    /// Mount point of the cgroup v2 filesystem. Default is `/sys/fs/cgroup`.
+    pub root: std::path::PathBuf,
+}
+
+impl Default for CgroupConfig {
+    fn default() -> Self {
+        Self {
+            root: std::path::PathBuf::from("/sys/fs/cgroup"),
+        }
+    }
+}
+
 pub struct Config {
-    pub signal: String,
+    pub signal: Signal,
     pub include_target: bool,
+    /// Ordered steps to escalate signals, e.g. `SIGTERM` and then `SIGKILL`.  
+    /// If empty, only `signal` is sent once and no waiting is done.  
+    /// If not empty, `signal` is not used.  
+    pub escalation: Vec<EscalationStep>,
+    /// If set, waits up to this timeout until every killed process has actually exited.  
+    /// The result is reported in `Output::Killed::exit_outcome`.  
+    pub wait_for_exit: Option<std::time::Duration>,
+    /// If `true`, stops every process in the tree with `SIGSTOP` from the top down before sending the signal, so that no process can fork while being killed.  
+    /// The process infos are queried again to stop the children forked in the meantime.  
+    /// After the signal is sent, the stopped processes are continued with `SIGCONT` so that the signal is handled.  
+    /// The target process is not stopped if `include_target` is `false`, and the current process is never stopped.  
+    /// Ignored on Windows.  
+    pub freeze: bool,
+    /// If set, the processes are queried and killed again until no live process is found in the tree of the target process, or a limit is reached.  
+    /// This kills the children spawned after the processes were queried.  
+    /// The outputs of all rounds are returned together, and each output has the index of its round.  
+    /// If the target process is killed, the children it spawned afterwards are no longer in its tree, so use it with `freeze` for such processes.  
+    pub repeat: Option<RepeatLimit>,
+    /// Which processes are killed in addition to the target process.  
+    /// Process groups and sessions are not supported on Windows, so only the descendants are killed.  
+    pub selection: Selection,
+    /// If set on Linux, the cgroup of the target process is resolved from `/proc/<pid>/cgroup`.  
+    /// If the processes to kill are exactly the processes in the cgroup and its descendant cgroups, they are killed at once by writing `1` to `cgroup.kill`.  
+    /// `cgroup.kill` always sends `SIGKILL`, so `signal` and `escalation` are not used then.  
+    /// Otherwise, or on other platforms, the signals are sent as usual.  
+    pub cgroup: Option<CgroupConfig>,
+    /// If set, it is called for each process found before killing, including the target process.  
+    /// Depending on the returned `FilterDecision`, the process is killed or skipped, and its child processes are visited or not.  
+    /// e.g. to keep a sidecar process alive while killing the others.  
+    /// The processes in the tree of the target process that are skipped are not killed by `selection` either.  
+    pub filter: Option<ProcessFilter>,
+    /// Max number of blocking tasks to send the signals at the same time in the `tokio` API.  
+    /// The processes of each depth are signalled at the same time, and the next depth is signalled after them, as ordered by `kill_order`.  
+    /// `0` is treated as `1`. Not used in the `blocking` API.  
+    pub signal_concurrency: usize,
+    /// Order in which the processes of a tree are killed. `LeavesFirst` by default.
+    pub kill_order: KillOrder,
+    /// What to do when a process cannot be signalled. `FailFast` by default.  
+    /// Errors that are not of a single process, e.g. querying the process infos, are always returned.  
+    pub error_policy: ErrorPolicy,
+    /// Mount point of procfs on Linux, which the processes are queried from. Default is `/proc`.  
+    /// e.g. `/host/proc` in a container that mounts the procfs of the host there.  
+    /// The target process ids are as listed there, and can be of another pid namespace, e.g. of the host or a container.  
+    /// They are translated with `NSpid` of status, so the outputs and signals use the process ids of the current pid namespace.  
+    /// A target process that is not visible from the current pid namespace is an `InvalidProcessId` error. Ignored on other platforms.  
+    /// Without `self`, e.g. a copy of some of its entries, the processes can be planned but not signalled,
+    /// since the process ids are not known to be the ones of the current pid namespace.  
+    pub proc_root: std::path::PathBuf,
+}
+
+impl std::fmt::Debug for Config {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Config")
+            .field("signal", &self.signal)
+            .field("include_target", &self.include_target)
+            .field("escalation", &self.escalation)
+            .field("wait_for_exit", &self.wait_for_exit)
+            .field("freeze", &self.freeze)
+            .field("repeat", &self.repeat)
+            .field("selection", &self.selection)
+            .field("cgroup", &self.cgroup)
+            .field("filter", &self.filter.as_ref().map(|_| "ProcessFilter"))
+            .field("signal_concurrency", &self.signal_concurrency)
+            .field("kill_order", &self.kill_order)
+            .field("error_policy", &self.error_policy)
+            .field("proc_root", &self.proc_root)
+            .finish()
+    }
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
-            signal: "SIGTERM".to_string(),
+            signal: Signal::SIGTERM,
             include_target: true,
+            escalation: Vec::new(),
+            wait_for_exit: None,
+            freeze: false,
+            repeat: None,
+            selection: Selection::Descendants,
+            cgroup: None,
+            filter: None,
+            signal_concurrency: 16,
+            kill_order: KillOrder::LeavesFirst,
+            error_policy: ErrorPolicy::FailFast,
+            proc_root: std::path::PathBuf::from("/proc"),
+        }
+    }
+}
+
+impl Config {
+    /// Returns a builder that validates the signals when the config is built.
+    #[must_use]
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::default()
+    }
+}
+
+/// Builder of `Config`. The signals are validated by `build`.  
+///
+/// # Examples
+///
+/// ```
+/// use kill_tree::{Config, Result, Signal};
+///
+/// fn main() -> Result<()> {
+///     let config = Config::builder()
+///         .escalation_step("SIGTERM", std::time::Duration::from_secs(1))
+///         .escalation_step("KILL", std::time::Duration::from_secs(1))
+///         .include_target(false)
+///         .build()?;
+///     assert_eq!(config.escalation[1].signal, Signal::SIGKILL);
+///     assert!(Config::builder().signal("SIGTRM").build().is_err());
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct ConfigBuilder {
+    signal: Option<String>,
+    escalation: Vec<(String, std::time::Duration)>,
+    config: Config,
+}
+
+impl ConfigBuilder {
+    #[must_use]
+    pub fn signal(mut self, signal: impl Into<String>) -> Self {
+        self.signal = Some(signal.into());
+        self
+    }
+
+    #[must_use]
+    pub fn include_target(mut self, include_target: bool) -> Self {
+        self.config.include_target = include_target;
+        self
+    }
+
+    /// Appends a step to `Config::escalation`.
+    #[must_use]
+    pub fn escalation_step(
+        mut self,
+        signal: impl Into<String>,
+        timeout: std::time::Duration,
+    ) -> Self {
+        self.escalation.push((signal.into(), timeout));
+        self
+    }
+
+    #[must_use]
+    pub fn wait_for_exit(mut self, timeout: std::time::Duration) -> Self {
+        self.config.wait_for_exit = Some(timeout);
+        self
+    }
+
+    #[must_use]
+    pub fn freeze(mut self, freeze: bool) -> Self {
+        self.config.freeze = freeze;
+        self
+    }
+
+    #[must_use]
+    pub fn repeat(mut self, repeat: RepeatLimit) -> Self {
+        self.config.repeat = Some(repeat);
+        self
+    }
+
+    #[must_use]
+    pub fn selection(mut self, selection: Selection) -> Self {
+        self.config.selection = selection;
+        self
+    }
+
+    #[must_use]
+    pub fn cgroup(mut self, cgroup: CgroupConfig) -> Self {
+        self.config.cgroup = Some(cgroup);
+        self
+    }
+
+    #[must_use]
+    pub fn filter(
+        mut self,
+        filter: impl Fn(&ProcessView) -> FilterDecision + Send + Sync + 'static,
+    ) -> Self {
+        self.config.filter = Some(Box::new(filter));
+        self
+    }
+
+    #[must_use]
+    pub fn signal_concurrency(mut self, signal_concurrency: usize) -> Self {
+        self.config.signal_concurrency = signal_concurrency;
+        self
+    }
+
+    #[must_use]
+    pub fn kill_order(mut self, kill_order: KillOrder) -> Self {
+        self.config.kill_order = kill_order;
+        self
+    }
+
+    #[must_use]
+    pub fn error_policy(mut self, error_policy: ErrorPolicy) -> Self {
+        self.config.error_policy = error_policy;
+        self
+    }
+
+    #[must_use]
+    pub fn proc_root(mut self, proc_root: impl Into<std::path::PathBuf>) -> Self {
+        self.config.proc_root = proc_root.into();
+        self
+    }
+
+    /// Parses the signals and returns the config.
+    ///
+    /// # Errors
+    ///
+    /// ## `InvalidSignal`
+    /// Returned when a signal is not supported on the current platform.  
+    pub fn build(self) -> Result<Config> {
+        let mut config = self.config;
+        if let Some(signal) = self.signal {
+            config.signal = signal.parse()?;
         }
+        config.escalation = self
+            .escalation
+            .into_iter()
+            .map(|(signal, timeout)| {
+                Ok(EscalationStep {
+                    signal: signal.parse()?,
+                    timeout,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(config)
+    }
+}
+
+/// In-memory process table, which provides the same process infos on every query.  
+/// e.g. to test which processes would be killed, without spawning real processes.  
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StaticProcessTable {
+    process_infos: ProcessInfos,
+}
+
+impl StaticProcessTable {
+    #[must_use]
+    pub fn new(process_infos: ProcessInfos) -> Self {
+        Self { process_infos }
+    }
+
+    /// Adds a process to the table.
+    #[must_use]
+    pub fn process(mut self, process_info: ProcessInfo) -> Self {
+        self.process_infos.push(process_info);
+        self
+    }
+
+    #[must_use]
+    pub fn process_infos(&self) -> &[ProcessInfo] {
+        &self.process_infos
+    }
+}
+
+impl From<ProcessInfos> for StaticProcessTable {
+    fn from(process_infos: ProcessInfos) -> Self {
+        Self::new(process_infos)
     }
 }
 
 #[cfg(feature = "blocking")]
 pub(crate) mod blocking {
-    use super::{ProcessInfos, Result};
+    use super::{ProcessInfos, Result, StaticProcessTable};
 
-    pub(crate) trait ProcessInfosProvidable {
+    /// Source of the process infos that the process trees are built from.  
+    /// Implement it to plug in your own data source, or use `StaticProcessTable`.  
+    pub trait ProcessInfosProvidable {
+        /// Returns the processes, which must include at least the target processes and their descendants.  
+        /// It is called again in every round, and after stopping the processes if `Config::freeze` is set.  
+        ///
+        /// # Errors
+        /// Returned as is by the function that queried the process infos.
         fn get_process_infos(&self) -> Result<ProcessInfos>;
     }
+
+    impl ProcessInfosProvidable for StaticProcessTable {
+        fn get_process_infos(&self) -> Result<ProcessInfos> {
+            Ok(self.process_infos.clone())
+        }
+    }
 }
 
 #[cfg(feature = "tokio")]
 pub(crate) mod tokio {
-    use super::{ProcessInfos, Result};
+    use super::{ProcessInfos, Result, StaticProcessTable};
 
-    pub(crate) trait ProcessInfosProvidable {
-        async fn get_process_infos(&self) -> Result<ProcessInfos>;
+    /// Source of the process infos that the process trees are built from.  
+    /// Implement it to plug in your own data source, or use `StaticProcessTable`.  
+    /// It is `Sync`, so that the futures of the kill functions can be sent between threads.  
+    pub trait ProcessInfosProvidable: Sync {
+        /// Returns the processes, which must include at least the target processes and their descendants.  
+        /// It is called again in every round, and after stopping the processes if `Config::freeze` is set.  
+        ///
+        /// # Errors
+        /// Returned as is by the function that queried the process infos.
+        fn get_process_infos(
+            &self,
+        ) -> impl std::future::Future<Output = Result<ProcessInfos>> + Send;
+    }
+
+    impl ProcessInfosProvidable for StaticProcessTable {
+        async fn get_process_infos(&self) -> Result<ProcessInfos> {
+            Ok(self.process_infos.clone())
+        }
     }
 }
 
@@ -254,7 +1260,215 @@ mod tests {
     #[test]
     fn default_config() {
         let config = Config::default();
-        assert_eq!(config.signal, "SIGTERM");
+        assert_eq!(config.signal, Signal::SIGTERM);
         assert!(config.include_target);
+        assert!(config.escalation.is_empty());
+        assert!(config.wait_for_exit.is_none());
+        assert!(!config.freeze);
+        assert!(config.repeat.is_none());
+        assert_eq!(config.selection, Selection::Descendants);
+        assert!(config.cgroup.is_none());
+        assert!(config.filter.is_none());
+        assert_eq!(config.signal_concurrency, 16);
+        assert_eq!(config.kill_order, KillOrder::LeavesFirst);
+        assert_eq!(config.error_policy, ErrorPolicy::FailFast);
+        assert_eq!(config.proc_root, std::path::PathBuf::from("/proc"));
+    }
+
+    #[test]
+    fn signal_from_str() {
+        for signal in ["SIGTERM", "TERM", "term", "15"] {
+            assert_eq!(signal.parse::<Signal>().unwrap(), Signal::SIGTERM);
+        }
+        assert_eq!("SIGKILL".parse::<Signal>().unwrap(), Signal::SIGKILL);
+        assert_eq!(Signal::SIGKILL.to_string(), "SIGKILL");
+    }
+
+    #[test]
+    fn signal_from_str_invalid() {
+        for signal in ["SIGTRM", "", "0", "-1", "SIG"] {
+            let result = signal.parse::<Signal>();
+            assert!(
+                matches!(result, Err(Error::InvalidSignal { .. })),
+                "{signal}: {result:?}"
+            );
+        }
+        assert_eq!(
+            "SIGTRM".parse::<Signal>().unwrap_err().to_string(),
+            "Invalid signal: SIGTRM. Reason: Unknown signal name"
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn signal_from_str_real_time() {
+        let signal = "SIGRTMIN+3".parse::<Signal>().unwrap();
+        assert_eq!(signal.number(), nix::libc::SIGRTMIN() + 3);
+        assert_eq!(signal.to_string(), "SIGRTMIN+3");
+        let signal = "RTMAX-1".parse::<Signal>().unwrap();
+        assert_eq!(signal.number(), nix::libc::SIGRTMAX() - 1);
+        assert!("SIGRTMIN+100".parse::<Signal>().is_err());
+    }
+
+    #[test]
+    fn config_builder() {
+        let config = Config::builder()
+            .signal("9")
+            .escalation_step("TERM", std::time::Duration::from_secs(1))
+            .include_target(false)
+            .build()
+            .unwrap();
+        assert_eq!(config.signal, Signal::SIGKILL);
+        assert_eq!(config.escalation.len(), 1);
+        assert_eq!(config.escalation[0].signal, Signal::SIGTERM);
+        assert!(!config.include_target);
+    }
+
+    #[test]
+    fn config_builder_invalid_signal() {
+        let result = Config::builder()
+            .escalation_step("SIGTRM", std::time::Duration::from_secs(1))
+            .build();
+        assert!(matches!(result, Err(Error::InvalidSignal { .. })));
+    }
+
+    #[test]
+    fn process_selector_name() {
+        let process_info = ProcessInfo {
+            process_id: 2,
+            parent_process_id: 1,
+            name: "node".to_string(),
+            ..Default::default()
+        };
+        let process = ProcessView {
+            process_info: &process_info,
+            depth: 0,
+            proc_root: std::path::Path::new("/proc"),
+        };
+        assert!(ProcessSelector::new().name("node").matches(&process));
+        assert!(!ProcessSelector::new().name("nod").matches(&process));
+        assert!(!ProcessSelector::new().matches(&process));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn process_selector_truncated_name() {
+        // the name of the test binary is longer than 15 bytes, so it is truncated
+        let program_name = std::env::current_exe()
+            .expect("Failed to get current exe")
+            .file_name()
+            .and_then(|x| x.to_str())
+            .expect("Failed to get file name")
+            .to_string();
+        assert!(program_name.len() > 15);
+        let process_info = ProcessInfo {
+            process_id: std::process::id(),
+            parent_process_id: 1,
+            name: program_name[..15].to_string(),
+            ..Default::default()
+        };
+        let process = ProcessView {
+            process_info: &process_info,
+            depth: 0,
+            proc_root: std::path::Path::new("/proc"),
+        };
+        assert!(ProcessSelector::new().name(&program_name).matches(&process));
+        assert!(!ProcessSelector::new()
+            .name(format!("{program_name}-other"))
+            .matches(&process));
+        assert!(!ProcessSelector::new().name("other").matches(&process));
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn static_process_table() {
+        use super::blocking::ProcessInfosProvidable;
+
+        let process_info = ProcessInfo {
+            process_id: 2,
+            parent_process_id: 1,
+            name: "node".to_string(),
+            ..Default::default()
+        };
+        let process_table =
+            StaticProcessTable::from(vec![process_info.clone()]).process(ProcessInfo {
+                process_id: 3,
+                parent_process_id: 2,
+                ..Default::default()
+            });
+        assert_eq!(process_table.process_infos().len(), 2);
+        let process_infos = process_table
+            .get_process_infos()
+            .expect("Failed to get process infos");
+        assert_eq!(process_infos[0], process_info);
+        assert_eq!(process_infos, process_table.get_process_infos().unwrap());
+    }
+
+    fn get_killed_output(process_id: ProcessId, parent_process_id: ParentProcessId) -> Output {
+        Output::Killed {
+            process_id,
+            parent_process_id,
+            name: process_id.to_string(),
+            ended_by_step: None,
+            exit_outcome: None,
+            round: 0,
+            process_info: ProcessInfo {
+                process_id,
+                parent_process_id,
+                name: process_id.to_string(),
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn kill_tree_report() {
+        // 1 -> 2 -> 4, 1 -> 3, in the order they were killed
+        let outputs = vec![
+            get_killed_output(4, 2),
+            get_killed_output(3, 1),
+            get_killed_output(2, 1),
+            get_killed_output(1, 0),
+        ];
+        let report = KillTreeReport::new(outputs);
+        assert_eq!(report.roots.len(), 1);
+        let pre_order = report
+            .pre_order()
+            .map(|x| (x.output.process_id(), x.depth))
+            .collect::<Vec<_>>();
+        assert_eq!(pre_order, vec![(1, 0), (2, 1), (4, 2), (3, 1)]);
+        let post_order = report
+            .post_order()
+            .map(|x| x.output.process_id())
+            .collect::<Vec<_>>();
+        assert_eq!(post_order, vec![4, 2, 3, 1]);
+        assert_eq!(
+            report.summary(),
+            KillTreeSummary {
+                total: 4,
+                killed: 4,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn kill_tree_report_cycle_and_unknown_parent() {
+        let outputs = vec![
+            get_killed_output(5, 6),
+            get_killed_output(6, 5),
+            Output::MaybeAlreadyTerminated {
+                process_id: 7,
+                source: std::io::Error::from(std::io::ErrorKind::NotFound).into(),
+                round: 0,
+                process_info: None,
+            },
+        ];
+        let report = KillTreeReport::new(outputs);
+        assert_eq!(report.pre_order().count(), 3);
+        assert_eq!(report.roots.len(), 2);
+        let summary = report.summary();
+        assert_eq!(summary.killed, 2);
+        assert_eq!(summary.maybe_already_terminated, 1);
     }
 }
@@ -16,4 +16,13 @@ pub mod blocking;
 pub mod tokio;
 
 pub use crate::common::get_available_max_process_id;
-pub use crate::core::{Config, Error, Output, Outputs, ParentProcessId, ProcessId, Result};
+#[cfg(target_os = "linux")]
+pub use crate::common::{reap_zombies, set_child_subreaper};
+pub use crate::core::{
+    CgroupConfig, CmdlineMatcher, Config, ConfigBuilder, Error, ErrorPolicy, EscalationStep,
+    ExitOutcome, FilterDecision, KillOrder, KillPlan, KillPlanEntry, KillReason, KillTreeNode,
+    KillTreePostOrder, KillTreePreOrder, KillTreeReport, KillTreeSummary, Output, Outputs,
+    ParentProcessId, ProcessExit, ProcessFilter, ProcessId, ProcessInfo, ProcessInfos,
+    ProcessSelector, ProcessView, RepeatLimit, Result, Selection, Signal, StaticProcessTable,
+    TreeOutputs,
+};
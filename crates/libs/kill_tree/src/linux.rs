@@ -1,10 +1,13 @@
-use crate::{
-    core::{Error, KillableBuildable, ProcessId, ProcessInfo, ProcessInfos, Result},
-    unix::Killer,
-    Config,
+use crate::core::{
+    Config, Error, ExitOutcome, KillOutput, Killable, KillableBuildable, Output, Outputs,
+    ProcessId, ProcessIds, ProcessInfo, ProcessInfos, Result, Selection, Signal,
 };
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
 use tracing::{debug, instrument};
 
+/// Default mount point of procfs. See `Config::proc_root`.
+const PROC_ROOT: &str = "/proc";
+
 /// In hexadecimal, 0x400000.  
 /// In decimal, 4194304.  
 pub(crate) const AVAILABLE_MAX_PROCESS_ID: u32 = 0x0040_0000;
@@ -13,197 +16,1064 @@ pub(crate) fn validate_process_id(process_id: ProcessId) -> Result<()> {
     crate::unix::validate_process_id(process_id, AVAILABLE_MAX_PROCESS_ID)
 }
 
-fn parse_status(process_id: ProcessId, status_path: String, status: &str) -> Result<ProcessInfo> {
-    let mut parent_process_id = None;
-    let mut name = None;
-    for line in status.lines() {
-        if parent_process_id.is_some() && name.is_some() {
-            break;
-        }
+/// Fields of stat, borrowed from the buffer it was read into.  
+struct Stat<'a> {
+    /// `comm`, which can contain whitespaces and parentheses.
+    name: &'a [u8],
+    state: u8,
+    parent_process_id: ProcessId,
+    process_group_id: ProcessId,
+    session_id: ProcessId,
+    /// Clock ticks since boot.
+    start_time: u64,
+}
 
-        if line.starts_with("PPid:") {
-            let Some(parent_process_id_str) = line.split_whitespace().nth(1) else {
-                return Err(Error::InvalidProcEntry {
-                    process_id,
-                    path: status_path,
-                    reason: "PPid line is invalid".into(),
-                    source: None,
-                });
-            };
+fn invalid_stat(
+    process_id: ProcessId,
+    reason: String,
+    source: Option<std::num::ParseIntError>,
+) -> Error {
+    Error::InvalidProcEntry {
+        process_id,
+        path: format!("/proc/{process_id}/stat"),
+        reason,
+        source,
+    }
+}
 
-            let parent_process_id_value = match parent_process_id_str.parse::<u32>() {
-                Ok(x) => x,
-                Err(e) => {
-                    return Err(Error::InvalidProcEntry {
-                        process_id,
-                        path: status_path,
-                        reason: "Failed to parse parent process id".into(),
-                        source: Some(e),
-                    });
-                }
-            };
+/// Parses a numeric field of stat without allocating.  
+fn parse_stat_field<T>(process_id: ProcessId, field: Option<&[u8]>, field_name: &str) -> Result<T>
+where
+    T: std::str::FromStr<Err = std::num::ParseIntError>,
+{
+    let Some(field) = field else {
+        return Err(invalid_stat(
+            process_id,
+            format!("{field_name} is None"),
+            None,
+        ));
+    };
+    let Ok(field) = std::str::from_utf8(field) else {
+        return Err(invalid_stat(
+            process_id,
+            format!("{field_name} is not UTF-8"),
+            None,
+        ));
+    };
+    field
+        .parse::<T>()
+        .map_err(|e| invalid_stat(process_id, format!("Failed to parse {field_name}"), Some(e)))
+}
 
-            parent_process_id = Some(parent_process_id_value);
+/// Parses stat without allocating, except on errors.  
+fn parse_stat(process_id: ProcessId, stat: &[u8]) -> Result<Stat<'_>> {
+    // The fields follow the name in parentheses, and the name can contain parentheses.
+    // The first field after the name is the state, which is the 3rd field.
+    let (Some(name_start), Some(name_end)) = (
+        stat.iter().position(|x| *x == b'('),
+        stat.iter().rposition(|x| *x == b')'),
+    ) else {
+        return Err(invalid_stat(process_id, "Name is not closed".into(), None));
+    };
+    if name_end < name_start {
+        return Err(invalid_stat(process_id, "Name is not closed".into(), None));
+    }
+    let mut fields = stat[name_end + 1..]
+        .split(u8::is_ascii_whitespace)
+        .filter(|x| !x.is_empty());
+    let Some(&[state]) = fields.next() else {
+        return Err(invalid_stat(process_id, "State is invalid".into(), None));
+    };
+    let parent_process_id = parse_stat_field(process_id, fields.next(), "parent process id")?;
+    let process_group_id = parse_stat_field(process_id, fields.next(), "process group id")?;
+    let session_id = parse_stat_field(process_id, fields.next(), "session id")?;
+    // the start time is the 22nd field, 16 fields after the session id
+    let start_time = parse_stat_field(process_id, fields.nth(15), "start time")?;
+    Ok(Stat {
+        name: &stat[name_start + 1..name_end],
+        state,
+        parent_process_id,
+        process_group_id,
+        session_id,
+        start_time,
+    })
+}
+
+/// Reads `<proc root>/<pid>/stat` of processes into one buffer, which is reused across reads.  
+struct StatReader<'a> {
+    proc_root: &'a std::path::Path,
+    path: Vec<u8>,
+    buffer: Vec<u8>,
+}
+
+impl Default for StatReader<'_> {
+    fn default() -> Self {
+        Self::new(std::path::Path::new(PROC_ROOT))
+    }
+}
+
+impl<'a> StatReader<'a> {
+    fn new(proc_root: &'a std::path::Path) -> Self {
+        Self {
+            proc_root,
+            path: Vec::new(),
+            buffer: Vec::new(),
         }
+    }
 
-        if line.starts_with("Name:") {
-            let name_value = if let Some(x) = line.split_whitespace().nth(1) {
-                x.to_string()
-            } else {
-                return Err(Error::InvalidProcEntry {
-                    process_id,
-                    path: status_path,
-                    reason: "Name line is invalid".into(),
-                    source: None,
-                });
-            };
+    /// Reads the stat and returns the metadata of the file with it.  
+    /// `None` if the process has exited in the meantime.  
+    fn read(&mut self, process_id: ProcessId) -> Result<Option<(Stat<'_>, std::fs::Metadata)>> {
+        use std::io::{Read as _, Write as _};
+        use std::os::unix::ffi::OsStrExt as _;
 
-            name = Some(name_value);
+        self.path.clear();
+        self.path
+            .extend_from_slice(self.proc_root.as_os_str().as_bytes());
+        let _ = write!(self.path, "/{process_id}/stat");
+        self.buffer.clear();
+        let path = std::ffi::OsStr::from_bytes(&self.path);
+        let result = std::fs::File::open(path).and_then(|mut file| {
+            file.read_to_end(&mut self.buffer)?;
+            file.metadata()
+        });
+        let metadata = match result {
+            Ok(x) => x,
+            // the process exited between listing and reading
+            Err(e) if is_exited_error(&e) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        if self.buffer.is_empty() {
+            return Ok(None);
         }
+        Ok(Some((parse_stat(process_id, &self.buffer)?, metadata)))
     }
 
-    let Some(parent_process_id) = parent_process_id else {
-        return Err(Error::InvalidProcEntry {
+    /// Reads the process info from the stat.  
+    /// The user and group ids are the owner of `/proc/<pid>`, which are the effective ids.  
+    /// `None` if the process has exited in the meantime.  
+    fn read_process_info(&mut self, process_id: ProcessId) -> Result<Option<ProcessInfo>> {
+        use std::os::unix::fs::MetadataExt as _;
+
+        let Some((stat, metadata)) = self.read(process_id)? else {
+            return Ok(None);
+        };
+        Ok(Some(ProcessInfo {
             process_id,
-            path: status_path,
-            reason: "Parent process id is None".into(),
-            source: None,
-        });
+            parent_process_id: stat.parent_process_id,
+            name: String::from_utf8_lossy(stat.name).into_owned(),
+            process_group_id: Some(stat.process_group_id),
+            session_id: Some(stat.session_id),
+            start_time: Some(stat.start_time),
+            user_id: Some(metadata.uid()),
+            group_id: Some(metadata.gid()),
+            state: Some(char::from(stat.state)),
+            cmdline: None,
+            exe: None,
+            namespace_process_ids: None,
+            namespace_process_group_ids: None,
+        }))
+    }
+}
+
+/// Returns the process id of a `/proc` entry, or `None` if it is not a process.  
+fn parse_proc_entry_name(file_name: &std::ffi::OsStr) -> Option<ProcessId> {
+    use std::os::unix::ffi::OsStrExt as _;
+
+    let bytes = file_name.as_bytes();
+    if bytes.is_empty() || !bytes.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    std::str::from_utf8(bytes).ok()?.parse::<ProcessId>().ok()
+}
+
+/// Scans the procfs mounted at `proc_root` reading only the stat of each process with one reused buffer.  
+/// Processes that exit during the scan are skipped.  
+#[instrument]
+fn get_process_infos(proc_root: &std::path::Path) -> Result<ProcessInfos> {
+    let mut reader = StatReader::new(proc_root);
+    let mut process_infos = ProcessInfos::new();
+    for entry_result in std::fs::read_dir(proc_root)? {
+        let entry = entry_result?;
+        let Some(process_id) = parse_proc_entry_name(&entry.file_name()) else {
+            continue;
+        };
+        match reader.read_process_info(process_id) {
+            Ok(Some(x)) => process_infos.push(x),
+            Ok(None) => {
+                debug!(process_id, "Process exited during scan");
+            }
+            Err(e) => {
+                debug!(process_id, error = ?e, "Failed to get process info");
+            }
+        }
+    }
+    Ok(process_infos)
+}
+
+/// Returns whether the error is because the process or thread has exited.
+fn is_exited_error(error: &std::io::Error) -> bool {
+    error.kind() == std::io::ErrorKind::NotFound || error.raw_os_error() == Some(nix::libc::ESRCH)
+}
+
+/// Returns the child process ids of the process, which are listed per thread in `/proc/<pid>/task/<tid>/children`.  
+/// Empty if the process has exited in the meantime.  
+fn get_child_process_ids(
+    proc_root: &std::path::Path,
+    process_id: ProcessId,
+    buffer: &mut Vec<u8>,
+) -> Result<ProcessIds> {
+    use std::io::Read as _;
+
+    let task_path = proc_root.join(process_id.to_string()).join("task");
+    let read_dir = match std::fs::read_dir(&task_path) {
+        Ok(x) => x,
+        Err(e) if is_exited_error(&e) => return Ok(ProcessIds::new()),
+        Err(e) => return Err(e.into()),
     };
+    let mut child_process_ids = ProcessIds::new();
+    for entry_result in read_dir {
+        let entry = match entry_result {
+            Ok(x) => x,
+            Err(e) if is_exited_error(&e) => continue,
+            Err(e) => return Err(e.into()),
+        };
+        let Some(thread_id) = parse_proc_entry_name(&entry.file_name()) else {
+            continue;
+        };
+        buffer.clear();
+        let result = std::fs::File::open(task_path.join(thread_id.to_string()).join("children"))
+            .and_then(|mut file| file.read_to_end(buffer));
+        match result {
+            Ok(_) => {}
+            // the thread exited between listing and reading
+            Err(e) if is_exited_error(&e) => continue,
+            Err(e) => return Err(e.into()),
+        }
+        child_process_ids.extend(
+            buffer
+                .split(u8::is_ascii_whitespace)
+                .filter_map(|x| std::str::from_utf8(x).ok()?.parse::<ProcessId>().ok()),
+        );
+    }
+    Ok(child_process_ids)
+}
 
-    let Some(name) = name else {
-        return Err(Error::InvalidProcEntry {
-            process_id,
-            path: status_path,
-            reason: "Name is None".into(),
-            source: None,
-        });
+/// Returns the process infos of the target processes and their descendants,
+/// walking down through `/proc/<pid>/task/<tid>/children` instead of scanning every process.  
+/// Falls back to scanning every process if the kernel does not provide children, which needs `CONFIG_PROC_CHILDREN`.  
+/// A child that is reparented during the walk is dropped later, since the tree is built by its parent process id.  
+#[instrument]
+fn get_tree_process_infos(
+    proc_root: &std::path::Path,
+    process_ids: &[ProcessId],
+) -> Result<ProcessInfos> {
+    if !proc_root.join("thread-self").join("children").exists() {
+        debug!("Children is not provided, scanning every process");
+        return get_process_infos(proc_root);
+    }
+    let mut reader = StatReader::new(proc_root);
+    let mut buffer = Vec::new();
+    let mut visited = std::collections::HashSet::new();
+    let mut queue = process_ids
+        .iter()
+        .copied()
+        .collect::<std::collections::VecDeque<_>>();
+    let mut process_infos = ProcessInfos::new();
+    while let Some(process_id) = queue.pop_front() {
+        if !visited.insert(process_id) {
+            continue;
+        }
+        match reader.read_process_info(process_id) {
+            Ok(Some(x)) => process_infos.push(x),
+            Ok(None) => {
+                debug!(process_id, "Process exited during walk");
+                continue;
+            }
+            Err(e) => {
+                debug!(process_id, error = ?e, "Failed to get process info");
+                continue;
+            }
+        }
+        queue.extend(get_child_process_ids(proc_root, process_id, &mut buffer)?);
+    }
+    Ok(process_infos)
+}
+
+/// Returns the target process ids to walk down from, or `None` to scan every process.  
+/// The process group or session of the target process can contain processes outside its tree.  
+fn get_walk_process_ids(process_ids: &[ProcessId], config: &Config) -> Option<ProcessIds> {
+    match config.selection {
+        Selection::Descendants => Some(process_ids.to_vec()),
+        Selection::ProcessGroup | Selection::Session => None,
+    }
+}
+
+/// `NSpid` and `NSpgid` of status, from the pid namespace of the procfs down to the one of the process.  
+#[derive(Debug, Default, PartialEq, Eq)]
+struct NamespaceProcessIds {
+    process_ids: Option<ProcessIds>,
+    process_group_ids: Option<ProcessIds>,
+}
+
+/// Parses the `NSpid:` and `NSpgid:` lines of status, which are provided since Linux 4.1.  
+fn parse_namespace_process_ids(status: &str) -> NamespaceProcessIds {
+    let parse = |value: &str| {
+        value
+            .split_ascii_whitespace()
+            .map(str::parse::<ProcessId>)
+            .collect::<std::result::Result<ProcessIds, _>>()
+            .ok()
+            .filter(|x| !x.is_empty())
     };
+    let mut namespace_process_ids = NamespaceProcessIds::default();
+    for line in status.lines() {
+        if let Some(value) = line.strip_prefix("NSpid:") {
+            namespace_process_ids.process_ids = parse(value);
+        } else if let Some(value) = line.strip_prefix("NSpgid:") {
+            namespace_process_ids.process_group_ids = parse(value);
+        }
+    }
+    namespace_process_ids
+}
 
-    Ok(ProcessInfo {
-        process_id,
-        parent_process_id,
-        name,
-    })
+/// Reads `NSpid` and `NSpgid` from `<proc root>/<pid>/status`.  
+/// `None` if the process has exited in the meantime.  
+fn read_namespace_process_ids(
+    proc_root: &std::path::Path,
+    process_id: ProcessId,
+) -> Result<Option<NamespaceProcessIds>> {
+    match std::fs::read_to_string(proc_root.join(process_id.to_string()).join("status")) {
+        Ok(x) => Ok(Some(parse_namespace_process_ids(&x))),
+        Err(e) if is_exited_error(&e) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
 }
 
-fn parse_proc_entry(process_id: ProcessId, path: &std::path::Path) -> Result<std::path::PathBuf> {
-    if !path.is_dir() {
-        return Err(Error::InvalidProcEntry {
-            process_id,
-            path: path.display().to_string(),
-            reason: "Proc entry is not a directory".into(),
-            source: None,
-        });
+/// Returns `NSpid` and `NSpgid` of the process.  
+/// `None` if there is no such process, it is not allowed to inquire, or the kernel does not provide them.  
+pub(crate) fn get_namespace_process_ids(
+    proc_root: &std::path::Path,
+    process_id: ProcessId,
+) -> (Option<ProcessIds>, Option<ProcessIds>) {
+    match read_namespace_process_ids(proc_root, process_id) {
+        Ok(Some(x)) => (x.process_ids, x.process_group_ids),
+        Ok(None) => (None, None),
+        Err(e) => {
+            debug!(process_id, error = ?e, "Failed to read status");
+            (None, None)
+        }
     }
+}
 
-    let Some(file_name) = path.file_name().and_then(|s| s.to_str()) else {
-        return Err(Error::InvalidProcEntry {
-            process_id,
-            path: path.display().to_string(),
-            reason: "Failed to get file name".into(),
-            source: None,
-        });
+/// Pid namespace of a procfs, relative to the one of the current process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PidNamespace {
+    /// The process ids listed in the procfs are valid for the current process.
+    Current,
+    /// The procfs is of an ancestor pid namespace, `level` levels above the current one, e.g. of the host.
+    Ancestor { level: usize },
+    /// The procfs is of a pid namespace nested in the current one, e.g. of a container.
+    Descendant,
+    /// There is no `self` in the procfs, e.g. a copy of some of its entries.
+    /// The process ids are used as listed, but they are not known to be the ones of the current pid namespace.
+    Unknown,
+}
+
+/// Detects the pid namespace of the procfs mounted at `proc_root`.  
+/// `<proc root>/self` points to the current process only if it is visible from the pid namespace of the procfs,
+/// and its `NSpid` there has one process id per level down to the current pid namespace.  
+fn get_pid_namespace(proc_root: &std::path::Path) -> Result<PidNamespace> {
+    if proc_root == std::path::Path::new(PROC_ROOT) {
+        return Ok(PidNamespace::Current);
+    }
+    let self_path = proc_root.join("self");
+    let target = match std::fs::read_link(&self_path) {
+        Ok(x) => x,
+        // not a procfs, e.g. a copy of some of its entries
+        Err(e)
+            if e.kind() == std::io::ErrorKind::NotFound
+                && self_path.symlink_metadata().is_err() =>
+        {
+            return Ok(PidNamespace::Unknown);
+        }
+        // the link exists, but the current process is not visible from the pid namespace of the procfs
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(PidNamespace::Descendant),
+        Err(e) => return Err(e.into()),
     };
+    let Some(process_id) = parse_proc_entry_name(target.as_os_str()) else {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("{} does not point to a process", self_path.display()),
+        )
+        .into());
+    };
+    let Some(namespace_process_ids) = read_namespace_process_ids(proc_root, process_id)? else {
+        return Ok(PidNamespace::Descendant);
+    };
+    match namespace_process_ids.process_ids {
+        Some(x) if x.len() > 1 => Ok(PidNamespace::Ancestor { level: x.len() - 1 }),
+        Some(_) => Ok(PidNamespace::Current),
+        None => {
+            debug!("NSpid is not provided, the process ids are used as listed");
+            Ok(PidNamespace::Current)
+        }
+    }
+}
+
+/// Translates the processes listed in the procfs of another pid namespace into the ones of the current pid namespace.  
+struct PidTranslator<'a> {
+    proc_root: &'a std::path::Path,
+    pid_namespace: PidNamespace,
+    /// Reads `/proc` of the current pid namespace.
+    reader: StatReader<'static>,
+    /// Process ids of the processes in nested pid namespaces by their innermost process id, built on first use.
+    nested_process_ids: Option<std::collections::HashMap<ProcessId, Vec<(ProcessId, ProcessIds)>>>,
+}
+
+impl<'a> PidTranslator<'a> {
+    fn new(proc_root: &'a std::path::Path, pid_namespace: PidNamespace) -> Self {
+        Self {
+            proc_root,
+            pid_namespace,
+            reader: StatReader::default(),
+            nested_process_ids: None,
+        }
+    }
+
+    /// Returns the candidates in the current pid namespace of the process with `NSpid` in the procfs.  
+    fn get_candidate_process_ids(&mut self, namespace_process_ids: &[ProcessId]) -> ProcessIds {
+        match self.pid_namespace {
+            PidNamespace::Current | PidNamespace::Unknown => {
+                namespace_process_ids.first().copied().into_iter().collect()
+            }
+            PidNamespace::Ancestor { level } => namespace_process_ids
+                .get(level)
+                .copied()
+                .into_iter()
+                .collect(),
+            PidNamespace::Descendant => {
+                let nested_process_ids = self
+                    .nested_process_ids
+                    .get_or_insert_with(get_nested_process_ids);
+                let Some(innermost) = namespace_process_ids.last() else {
+                    return ProcessIds::new();
+                };
+                nested_process_ids
+                    .get(innermost)
+                    .into_iter()
+                    .flatten()
+                    .filter(|(_, x)| x.ends_with(namespace_process_ids))
+                    .map(|(process_id, _)| *process_id)
+                    .collect()
+            }
+        }
+    }
+
+    /// Returns the process info read from `/proc` of the current pid namespace, with `NSpid` and `NSpgid` of the procfs.  
+    /// `None` if the process is not visible from the current pid namespace, or has exited in the meantime.  
+    fn translate(&mut self, process_info: &ProcessInfo) -> Result<Option<ProcessInfo>> {
+        let Some(namespace_process_ids) =
+            read_namespace_process_ids(self.proc_root, process_info.process_id)?
+        else {
+            return Ok(None);
+        };
+        let Some(process_ids) = namespace_process_ids.process_ids else {
+            return Ok(None);
+        };
+        for process_id in self.get_candidate_process_ids(&process_ids) {
+            let Some(mut translated) = self.reader.read_process_info(process_id)? else {
+                continue;
+            };
+            // the process id can be reused by another process
+            if translated.start_time != process_info.start_time {
+                continue;
+            }
+            translated.namespace_process_ids = Some(process_ids);
+            translated.namespace_process_group_ids = namespace_process_ids.process_group_ids;
+            return Ok(Some(translated));
+        }
+        Ok(None)
+    }
+}
 
-    let process_id = match file_name.parse::<u32>() {
+/// Returns the processes in nested pid namespaces by their innermost process id, with their `NSpid`.  
+fn get_nested_process_ids() -> std::collections::HashMap<ProcessId, Vec<(ProcessId, ProcessIds)>> {
+    let proc_root = std::path::Path::new(PROC_ROOT);
+    let mut nested_process_ids = std::collections::HashMap::<_, Vec<_>>::new();
+    let read_dir = match std::fs::read_dir(proc_root) {
         Ok(x) => x,
         Err(e) => {
-            return Err(Error::InvalidProcEntry {
-                process_id,
-                path: path.display().to_string(),
-                reason: "Failed to parse process id".into(),
-                source: Some(e),
-            });
+            debug!(error = ?e, "Failed to read /proc");
+            return nested_process_ids;
         }
     };
+    for entry in read_dir.flatten() {
+        let Some(process_id) = parse_proc_entry_name(&entry.file_name()) else {
+            continue;
+        };
+        let Ok(Some(NamespaceProcessIds {
+            process_ids: Some(process_ids),
+            ..
+        })) = read_namespace_process_ids(proc_root, process_id)
+        else {
+            continue;
+        };
+        if let (true, Some(&innermost)) = (process_ids.len() > 1, process_ids.last()) {
+            nested_process_ids
+                .entry(innermost)
+                .or_default()
+                .push((process_id, process_ids));
+        }
+    }
+    nested_process_ids
+}
 
-    let status_path = path.join("status");
-    if !status_path.exists() {
-        return Err(Error::InvalidProcEntry {
-            process_id,
-            path: status_path.display().to_string(),
-            reason: "Status path does not exist".into(),
-            source: None,
-        });
+/// Returns the process infos from the procfs mounted at `proc_root`, with the process ids of the current pid namespace.  
+/// Only the target processes and their descendants are returned, or every process if `process_ids` is `None`.  
+/// Processes not visible from the current pid namespace are dropped, since they cannot be signalled.  
+#[instrument]
+fn get_procfs_process_infos(
+    proc_root: &std::path::Path,
+    process_ids: Option<&[ProcessId]>,
+) -> Result<ProcessInfos> {
+    let process_infos = match process_ids {
+        Some(x) => get_tree_process_infos(proc_root, x)?,
+        None => get_process_infos(proc_root)?,
+    };
+    let pid_namespace = get_pid_namespace(proc_root)?;
+    if matches!(pid_namespace, PidNamespace::Current | PidNamespace::Unknown) {
+        return Ok(process_infos);
     }
+    let mut translator = PidTranslator::new(proc_root, pid_namespace);
+    let mut translated_process_infos = ProcessInfos::with_capacity(process_infos.len());
+    for process_info in &process_infos {
+        let process_id = process_info.process_id;
+        match translator.translate(process_info) {
+            Ok(Some(x)) => translated_process_infos.push(x),
+            Ok(None) => {
+                debug!(
+                    process_id,
+                    "Process is not visible from the current pid namespace"
+                );
+            }
+            Err(e) => {
+                debug!(process_id, error = ?e, "Failed to translate process id");
+            }
+        }
+    }
+    Ok(translated_process_infos)
+}
 
-    if !status_path.is_file() {
-        return Err(Error::InvalidProcEntry {
+/// Returns the process id in the current pid namespace of the target process listed in `Config::proc_root`.  
+pub(crate) fn get_caller_process_id(process_id: ProcessId, config: &Config) -> Result<ProcessId> {
+    let pid_namespace = get_pid_namespace(&config.proc_root)?;
+    if matches!(pid_namespace, PidNamespace::Current | PidNamespace::Unknown) {
+        return Ok(process_id);
+    }
+    let process_info = StatReader::new(&config.proc_root).read_process_info(process_id)?;
+    let translated = match process_info {
+        Some(x) => PidTranslator::new(&config.proc_root, pid_namespace).translate(&x)?,
+        None => None,
+    };
+    match translated {
+        Some(x) => Ok(x.process_id),
+        None => Err(Error::InvalidProcessId {
             process_id,
-            path: status_path.display().to_string(),
-            reason: "Status path is not a file".into(),
-            source: None,
-        });
+            reason: "Not visible from the pid namespace of the current process".into(),
+        }),
+    }
+}
+
+/// Returns the procfs to read the processes provided for `Config::proc_root` from.  
+/// The processes of another pid namespace are translated into the ones of the current pid namespace,
+/// so they are read from `/proc`.  
+pub(crate) fn get_read_proc_root(proc_root: &std::path::Path) -> Result<&std::path::Path> {
+    match get_pid_namespace(proc_root)? {
+        PidNamespace::Current | PidNamespace::Unknown => Ok(proc_root),
+        PidNamespace::Ancestor { .. } | PidNamespace::Descendant => {
+            Ok(std::path::Path::new(PROC_ROOT))
+        }
     }
+}
 
-    Ok(status_path)
+/// Returns the procfs to read the processes to signal from, like `get_read_proc_root`.  
+/// Refuses the procfs of an unknown pid namespace,
+/// since its process ids can belong to other processes in the current pid namespace.  
+pub(crate) fn get_signal_proc_root(proc_root: &std::path::Path) -> Result<&std::path::Path> {
+    match get_pid_namespace(proc_root)? {
+        PidNamespace::Current => Ok(proc_root),
+        PidNamespace::Ancestor { .. } | PidNamespace::Descendant => {
+            Ok(std::path::Path::new(PROC_ROOT))
+        }
+        PidNamespace::Unknown => Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!(
+                "{} has no self, so its process ids cannot be signalled",
+                proc_root.display()
+            ),
+        )
+        .into()),
+    }
 }
 
 pub(crate) fn child_process_id_map_filter(_process_info: &ProcessInfo) -> bool {
     false
 }
 
-pub(crate) struct KillerBuilder {}
+/// Returns whether the process has exited, has become a zombie or is still alive.  
+pub(crate) fn get_exit_outcome(
+    proc_root: &std::path::Path,
+    process_id: ProcessId,
+) -> Result<ExitOutcome> {
+    let mut reader = StatReader::new(proc_root);
+    let stat = match reader.read(process_id) {
+        Ok(Some((x, _))) => x,
+        Ok(None) => return Ok(ExitOutcome::Exited),
+        Err(Error::Io(e)) => {
+            debug!(process_id, error = ?e, "Failed to read stat");
+            return crate::unix::get_exit_outcome(process_id);
+        }
+        Err(e) => return Err(e),
+    };
+    match stat.state {
+        b'Z' => Ok(ExitOutcome::Zombie),
+        b'X' => Ok(ExitOutcome::Exited),
+        _ => Ok(ExitOutcome::StillAlive),
+    }
+}
+
+/// Returns the path of the executable of the process.  
+/// `None` if there is no such process, or it is not allowed to inquire.  
+pub(crate) fn get_exe(
+    proc_root: &std::path::Path,
+    process_id: ProcessId,
+) -> Option<std::path::PathBuf> {
+    match std::fs::read_link(proc_root.join(process_id.to_string()).join("exe")) {
+        Ok(x) => Some(x),
+        Err(e) => {
+            debug!(process_id, error = ?e, "Failed to read exe");
+            None
+        }
+    }
+}
+
+/// Returns the command line arguments of the process.  
+/// `None` if there is no such process, or it is not allowed to inquire.  
+pub(crate) fn get_cmdline(
+    proc_root: &std::path::Path,
+    process_id: ProcessId,
+) -> Option<Vec<String>> {
+    let cmdline = match std::fs::read(proc_root.join(process_id.to_string()).join("cmdline")) {
+        Ok(x) => x,
+        Err(e) => {
+            debug!(process_id, error = ?e, "Failed to read cmdline");
+            return None;
+        }
+    };
+    // arguments are terminated by NUL
+    Some(
+        cmdline
+            .split(|x| *x == 0)
+            .filter(|x| !x.is_empty())
+            .map(|x| String::from_utf8_lossy(x).into_owned())
+            .collect(),
+    )
+}
+
+/// Returns the start time of the process that has the process id now.  
+/// `None` if there is no such process.  
+pub(crate) fn get_start_time(
+    proc_root: &std::path::Path,
+    process_id: ProcessId,
+) -> Result<Option<u64>> {
+    let mut reader = StatReader::new(proc_root);
+    Ok(reader.read(process_id)?.map(|(stat, _)| stat.start_time))
+}
+
+fn pidfd_open(process_id: ProcessId) -> Result<OwnedFd> {
+    let process_id_sign = i32::try_from(process_id).map_err(|e| Error::InvalidCast {
+        reason: "Failed to cast process id to i32".into(),
+        source: e,
+    })?;
+    let result = unsafe { nix::libc::syscall(nix::libc::SYS_pidfd_open, process_id_sign, 0) };
+    if result < 0 {
+        return Err(nix::errno::Errno::last().into());
+    }
+    let fd = i32::try_from(result).map_err(|e| Error::InvalidCast {
+        reason: "Failed to cast pidfd to i32".into(),
+        source: e,
+    })?;
+    Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+}
+
+fn pidfd_send_signal(pidfd: &OwnedFd, signal: Signal) -> nix::Result<()> {
+    let result = unsafe {
+        nix::libc::syscall(
+            nix::libc::SYS_pidfd_send_signal,
+            pidfd.as_raw_fd(),
+            signal.number(),
+            std::ptr::null::<nix::libc::siginfo_t>(),
+            0,
+        )
+    };
+    if result < 0 {
+        return Err(nix::errno::Errno::last());
+    }
+    Ok(())
+}
+
+enum Pidfd {
+    Opened(OwnedFd),
+    /// The process has terminated.
+    Gone,
+}
+
+fn open_pidfd(process_id: ProcessId) -> Result<Pidfd> {
+    match pidfd_open(process_id) {
+        Ok(x) => Ok(Pidfd::Opened(x)),
+        Err(Error::Unix(nix::errno::Errno::ESRCH)) => Ok(Pidfd::Gone),
+        Err(e) => Err(e),
+    }
+}
+
+/// Pidfds opened by `open_exit_pidfds`.
+#[cfg(feature = "tokio")]
+struct ExitPidfds {
+    pidfds: Vec<(ProcessId, OwnedFd)>,
+    /// Process ids of the processes that have already exited.
+    gone_process_ids: ProcessIds,
+    /// Processes whose pidfd could not be opened, with their start times, to poll instead.
+    poll_targets: Vec<crate::common::ExitTarget>,
+}
+
+/// Opens a pidfd for each process to wait for its exit.  
+/// The start time is checked after the pidfd is opened, so a process whose process id was reused is never waited on.  
+/// A process is polled instead if its pidfd cannot be opened, e.g. with `EMFILE` in a tree larger than the open files limit.  
+/// Returns `ENOSYS` if pidfd is not supported by the kernel (before Linux 5.3).  
+/// The killed process ids are the ones of the current pid namespace, so `/proc` is read.  
+#[cfg(feature = "tokio")]
+fn open_exit_pidfds(targets: &[crate::common::ExitTarget]) -> Result<ExitPidfds> {
+    let proc_root = std::path::Path::new(PROC_ROOT);
+    let mut exit_pidfds = ExitPidfds {
+        pidfds: Vec::new(),
+        gone_process_ids: ProcessIds::new(),
+        poll_targets: Vec::new(),
+    };
+    for &(process_id, start_time) in targets {
+        let pidfd = match open_pidfd(process_id) {
+            Ok(Pidfd::Opened(x)) => x,
+            Ok(Pidfd::Gone) => {
+                exit_pidfds.gone_process_ids.push(process_id);
+                continue;
+            }
+            Err(e @ Error::Unix(nix::errno::Errno::ENOSYS)) => return Err(e),
+            Err(e) => {
+                debug!(process_id, error = ?e, "Failed to open pidfd, exit is polled instead");
+                exit_pidfds.poll_targets.push((process_id, start_time));
+                continue;
+            }
+        };
+        match crate::common::is_process_id_reused(proc_root, process_id, start_time) {
+            Ok(true) => exit_pidfds.gone_process_ids.push(process_id),
+            Ok(false) => exit_pidfds.pidfds.push((process_id, pidfd)),
+            Err(e) => {
+                debug!(process_id, error = ?e, "Failed to check start time, exit is polled instead");
+                exit_pidfds.poll_targets.push((process_id, start_time));
+            }
+        }
+    }
+    Ok(exit_pidfds)
+}
+
+/// Signals through pidfds, so that a signal never reaches another process that reused the process id.  
+/// The pidfd refers to the process that had the process id when it was opened.  
+/// The start time is checked before each signal, so if it still matches, the pidfd refers to the process in the snapshot.  
+/// Falls back to `kill` if pidfd is not supported by the kernel (before Linux 5.3).  
+pub(crate) struct Killer<'a> {
+    killer: crate::unix::Killer,
+    pidfds: &'a std::collections::HashMap<ProcessId, Pidfd>,
+}
+
+impl Killable for Killer<'_> {
+    fn kill(&self, process_id: ProcessId) -> Result<KillOutput> {
+        match self.pidfds.get(&process_id) {
+            Some(Pidfd::Opened(pidfd)) => match pidfd_send_signal(pidfd, self.killer.signal) {
+                Ok(()) => Ok(KillOutput::Killed { process_id }),
+                // ESRCH: The process has terminated.
+                // This treat as success.
+                Err(nix::errno::Errno::ESRCH) => Ok(KillOutput::MaybeAlreadyTerminated {
+                    process_id,
+                    source: nix::errno::Errno::ESRCH.into(),
+                }),
+                Err(e) => Err(e.into()),
+            },
+            Some(Pidfd::Gone) => Ok(KillOutput::MaybeAlreadyTerminated {
+                process_id,
+                source: nix::errno::Errno::ESRCH.into(),
+            }),
+            None => self.killer.kill(process_id),
+        }
+    }
+}
+
+pub(crate) struct KillerBuilder {
+    pidfds: std::collections::HashMap<ProcessId, Pidfd>,
+}
+
+impl KillerBuilder {
+    /// Opens pidfds for the processes before any signal is sent.  
+    /// Processes without a pidfd are signalled by `kill`.  
+    pub(crate) fn new(process_ids: &[ProcessId]) -> Self {
+        let mut pidfds = std::collections::HashMap::new();
+        for &process_id in process_ids {
+            match open_pidfd(process_id) {
+                Ok(pidfd) => {
+                    pidfds.insert(process_id, pidfd);
+                }
+                Err(Error::Unix(nix::errno::Errno::ENOSYS)) => {
+                    debug!("pidfd is not supported, kill is used instead");
+                    break;
+                }
+                Err(e) => {
+                    debug!(process_id, error = ?e, "Failed to open pidfd, kill is used instead");
+                }
+            }
+        }
+        Self { pidfds }
+    }
+}
 
 impl KillableBuildable for KillerBuilder {
-    fn new_killable(&self, config: &Config) -> Result<Killer> {
+    fn new_killable(&self, signal: Signal) -> Result<Killer<'_>> {
         let killer_builder = crate::unix::KillerBuilder {};
-        killer_builder.new_killable(config)
+        let killer = killer_builder.new_killable(signal)?;
+        Ok(Killer {
+            killer,
+            pidfds: &self.pidfds,
+        })
+    }
+}
+
+/// Returns the number of the signal, e.g. `TERM` or `RTMIN+3`, without the `SIG` prefix.
+pub(crate) fn parse_signal_name(name: &str) -> Option<i32> {
+    let real_time = if let Some(offset) = name.strip_prefix("RTMIN") {
+        Some(nix::libc::SIGRTMIN() + parse_real_time_offset(offset, '+')?)
+    } else if let Some(offset) = name.strip_prefix("RTMAX") {
+        Some(nix::libc::SIGRTMAX() - parse_real_time_offset(offset, '-')?)
+    } else {
+        None
+    };
+    match real_time {
+        Some(number) if is_real_time_signal(number) => Some(number),
+        Some(_) => None,
+        None => crate::unix::parse_signal_name(name),
+    }
+}
+
+/// Parses the offset of a real-time signal, e.g. `+3` of `RTMIN+3`.
+fn parse_real_time_offset(offset: &str, sign: char) -> Option<i32> {
+    if offset.is_empty() {
+        return Some(0);
+    }
+    offset.strip_prefix(sign)?.parse::<i32>().ok()
+}
+
+fn is_real_time_signal(number: i32) -> bool {
+    (nix::libc::SIGRTMIN()..=nix::libc::SIGRTMAX()).contains(&number)
+}
+
+/// `None` if the signal number is not supported.
+pub(crate) fn get_signal_name(number: i32) -> Option<String> {
+    if !is_real_time_signal(number) {
+        return crate::unix::get_signal_name(number);
+    }
+    match number - nix::libc::SIGRTMIN() {
+        0 => Some("SIGRTMIN".to_string()),
+        offset => Some(format!("SIGRTMIN+{offset}")),
+    }
+}
+
+/// Returns the path of the cgroup v2 entry, which is the line starting with `0::`.
+fn parse_cgroup(cgroup: &str) -> Option<&str> {
+    cgroup.lines().find_map(|line| line.strip_prefix("0::"))
+}
+
+/// Returns the cgroup v2 directory of the process under the cgroup root.  
+/// `None` if the process is not in a cgroup v2 hierarchy.  
+pub(crate) fn get_cgroup_path(
+    proc_root: &std::path::Path,
+    cgroup_root: &std::path::Path,
+    process_id: ProcessId,
+) -> Result<Option<std::path::PathBuf>> {
+    let cgroup = std::fs::read_to_string(proc_root.join(process_id.to_string()).join("cgroup"))?;
+    Ok(parse_cgroup(&cgroup).map(|x| cgroup_root.join(x.trim_start_matches('/'))))
+}
+
+/// Returns the process ids in the cgroup and its descendant cgroups, which are all killed by `cgroup.kill`.  
+/// Cgroup files are in memory, so reading them does not block.  
+pub(crate) fn get_cgroup_process_ids(cgroup_path: &std::path::Path) -> Result<ProcessIds> {
+    let mut process_ids = ProcessIds::new();
+    let mut cgroup_paths = vec![cgroup_path.to_path_buf()];
+    while let Some(cgroup_path) = cgroup_paths.pop() {
+        let procs = std::fs::read_to_string(cgroup_path.join("cgroup.procs"))?;
+        for line in procs.lines() {
+            match line.trim().parse::<u32>() {
+                Ok(x) => process_ids.push(x),
+                Err(e) => {
+                    debug!(line, error = ?e, "Failed to parse process id");
+                }
+            }
+        }
+        for entry_result in std::fs::read_dir(&cgroup_path)? {
+            let entry = entry_result?;
+            if entry.file_type()?.is_dir() {
+                cgroup_paths.push(entry.path());
+            }
+        }
+    }
+    process_ids.sort_unstable();
+    Ok(process_ids)
+}
+
+/// Kills all processes in the cgroup and its descendant cgroups with `SIGKILL` at once.  
+/// `cgroup.kill` is supported since Linux 5.14.  
+pub(crate) fn write_cgroup_kill(cgroup_path: &std::path::Path) -> Result<()> {
+    use std::io::Write;
+
+    // do not create the file if it does not exist
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .open(cgroup_path.join("cgroup.kill"))?;
+    file.write_all(b"1")?;
+    Ok(())
+}
+
+/// Returns the cgroup of the target process, if the processes to kill are exactly the processes in it.
+pub(crate) fn get_cgroup_path_to_kill(
+    proc_root: &std::path::Path,
+    cgroup_root: &std::path::Path,
+    target_process_id: ProcessId,
+    process_ids_to_kill: &[ProcessId],
+) -> Option<std::path::PathBuf> {
+    let cgroup_path = match get_cgroup_path(proc_root, cgroup_root, target_process_id) {
+        Ok(Some(x)) => x,
+        Ok(None) => {
+            debug!(target_process_id, "Cgroup v2 is not found");
+            return None;
+        }
+        Err(e) => {
+            debug!(target_process_id, error = ?e, "Failed to get cgroup path");
+            return None;
+        }
+    };
+    let cgroup_process_ids = match get_cgroup_process_ids(&cgroup_path) {
+        Ok(x) => x,
+        Err(e) => {
+            debug!(cgroup_path = ?cgroup_path, error = ?e, "Failed to get cgroup process ids");
+            return None;
+        }
+    };
+    let mut process_ids_to_kill = process_ids_to_kill.to_vec();
+    process_ids_to_kill.sort_unstable();
+    if cgroup_process_ids != process_ids_to_kill {
+        debug!(cgroup_path = ?cgroup_path, "Processes to kill do not match the cgroup");
+        return None;
+    }
+    Some(cgroup_path)
+}
+
+/// Kills the cgroup and returns the outputs of the processes that were in it.  
+/// The processes not found in the process infos have terminated before being killed.  
+pub(crate) fn kill_cgroup(
+    cgroup_path: &std::path::Path,
+    process_ids: ProcessIds,
+    process_infos: ProcessInfos,
+) -> Result<Outputs> {
+    write_cgroup_kill(cgroup_path)?;
+    let mut process_info_map = crate::common::get_process_info_map(process_infos);
+    let outputs = process_ids
+        .into_iter()
+        .map(|process_id| {
+            crate::common::parse_kill_output(
+                KillOutput::Killed { process_id },
+                &mut process_info_map,
+                0,
+            )
+            .unwrap_or(Output::MaybeAlreadyTerminated {
+                process_id,
+                source: std::io::Error::from(std::io::ErrorKind::NotFound).into(),
+                round: 0,
+                process_info: None,
+            })
+        })
+        .collect();
+    Ok(outputs)
+}
+
+/// Makes the current process a child subreaper.  
+/// Orphaned descendants are reparented to the current process instead of init.  
+pub(crate) fn set_child_subreaper() -> Result<()> {
+    nix::sys::prctl::set_child_subreaper(true)?;
+    Ok(())
+}
+
+/// Reaps the zombie children of the current process, except `exclude_process_ids`, and returns their process ids.  
+/// The children are in the pid namespace of the current process, so `/proc` is read.  
+#[instrument]
+pub(crate) fn reap_zombies(exclude_process_ids: &[ProcessId]) -> Result<ProcessIds> {
+    let proc_root = std::path::Path::new(PROC_ROOT);
+    let current_process_id = std::process::id();
+    let mut process_ids = ProcessIds::new();
+    let mut reader = StatReader::new(proc_root);
+    for entry_result in std::fs::read_dir(proc_root)? {
+        let entry = entry_result?;
+        let Some(process_id) = parse_proc_entry_name(&entry.file_name()) else {
+            continue;
+        };
+        let stat = match reader.read(process_id) {
+            Ok(Some((x, _))) => x,
+            Ok(None) => continue,
+            Err(e) => {
+                debug!(process_id, error = ?e, "Failed to read stat");
+                continue;
+            }
+        };
+        if stat.parent_process_id != current_process_id
+            || stat.state != b'Z'
+            || exclude_process_ids.contains(&process_id)
+        {
+            continue;
+        }
+        let process_id_sign = i32::try_from(process_id).map_err(|e| Error::InvalidCast {
+            reason: "Failed to cast process id to i32".into(),
+            source: e,
+        })?;
+        match nix::sys::wait::waitpid(
+            nix::unistd::Pid::from_raw(process_id_sign),
+            Some(nix::sys::wait::WaitPidFlag::WNOHANG),
+        ) {
+            Ok(nix::sys::wait::WaitStatus::StillAlive) => {}
+            Ok(_) => process_ids.push(process_id),
+            Err(nix::errno::Errno::ECHILD) => {
+                // reaped by someone else in the meantime
+                debug!(process_id, "Already reaped");
+            }
+            Err(e) => return Err(e.into()),
+        }
     }
+    process_ids.sort_unstable();
+    Ok(process_ids)
 }
 
 #[cfg(feature = "blocking")]
 pub(crate) mod blocking {
-    use super::{
-        debug, instrument, parse_proc_entry, parse_status, ProcessId, ProcessInfo, ProcessInfos,
-        Result,
-    };
+    use super::{instrument, Config, ProcessId, ProcessIds, ProcessInfos, Result};
     use crate::core::blocking::ProcessInfosProvidable;
 
-    #[instrument]
-    pub(crate) fn get_process_info(
-        process_id: ProcessId,
-        path: std::path::PathBuf,
-    ) -> Result<ProcessInfo> {
-        let status_path = parse_proc_entry(process_id, &path)?;
-        let status = match std::fs::read_to_string(&status_path) {
-            Ok(x) => x,
-            Err(e) => {
-                return Err(e.into());
-            }
-        };
-        parse_status(process_id, status_path.display().to_string(), &status)
-    }
-
     #[instrument]
     pub(crate) fn get_process_infos() -> Result<ProcessInfos> {
-        let read_dir = std::fs::read_dir("/proc")?;
-        let mut process_infos = ProcessInfos::new();
-        for entry_result in read_dir {
-            let entry = entry_result?;
-            let file_name = entry.file_name();
-            let Some(file_name) = file_name.to_str() else {
-                debug!(file_name = ?file_name, "Failed to convert file name to str");
-                continue;
-            };
-            let process_id = match file_name.parse::<u32>() {
-                Ok(x) => x,
-                Err(e) => {
-                    debug!(file_name, error = ?e, "Failed to parse process id");
-                    continue;
-                }
-            };
-            let process_info = match get_process_info(process_id, entry.path()) {
-                Ok(x) => x,
-                Err(e) => {
-                    debug!(error = ?e, "Failed to get process info");
-                    continue;
-                }
-            };
-            process_infos.push(process_info);
-        }
-        Ok(process_infos)
+        super::get_process_infos(std::path::Path::new(super::PROC_ROOT))
     }
 
     pub(crate) struct ProcessInfosProvider {}
@@ -213,66 +1083,199 @@ pub(crate) mod blocking {
             crate::linux::blocking::get_process_infos()
         }
     }
+
+    /// Provides the processes from the procfs mounted at `proc_root`, with the process ids of the current pid namespace.  
+    /// Only the target processes and their descendants are provided, or every process if `process_ids` is `None`.  
+    pub(crate) struct ProcfsProcessInfosProvider {
+        proc_root: std::path::PathBuf,
+        process_ids: Option<ProcessIds>,
+    }
+
+    impl ProcessInfosProvidable for ProcfsProcessInfosProvider {
+        fn get_process_infos(&self) -> Result<ProcessInfos> {
+            super::get_procfs_process_infos(&self.proc_root, self.process_ids.as_deref())
+        }
+    }
+
+    pub(crate) fn new_process_infos_provider(config: &Config) -> ProcfsProcessInfosProvider {
+        ProcfsProcessInfosProvider {
+            proc_root: config.proc_root.clone(),
+            process_ids: None,
+        }
+    }
+
+    pub(crate) fn new_tree_process_infos_provider(
+        process_ids: &[ProcessId],
+        config: &Config,
+    ) -> ProcfsProcessInfosProvider {
+        ProcfsProcessInfosProvider {
+            proc_root: config.proc_root.clone(),
+            process_ids: super::get_walk_process_ids(process_ids, config),
+        }
+    }
 }
 
 #[cfg(feature = "tokio")]
 pub(crate) mod tokio {
     use super::{
-        debug, instrument, parse_proc_entry, parse_status, Error, ProcessId, ProcessInfo,
-        ProcessInfos, Result,
+        debug, instrument, Config, Error, ExitOutcome, ProcessId, ProcessIds, ProcessInfos, Result,
     };
-    use crate::core::tokio::ProcessInfosProvidable;
+    use crate::core::{tokio::ProcessInfosProvidable, ProcessExit};
+    use ::tokio::io::{unix::AsyncFd, Interest};
 
+    /// Runs the scan on a blocking task, since reading procfs never waits on I/O.  
     #[instrument]
-    pub(crate) async fn get_process_info(
-        process_id: ProcessId,
-        path: std::path::PathBuf,
-    ) -> Result<ProcessInfo> {
-        let status_path = parse_proc_entry(process_id, &path)?;
-        let status = match ::tokio::fs::read_to_string(&status_path).await {
+    pub(crate) async fn get_process_infos() -> Result<ProcessInfos> {
+        ::tokio::task::spawn_blocking(|| {
+            super::get_process_infos(std::path::Path::new(super::PROC_ROOT))
+        })
+        .await
+        .map_err(crate::common::tokio::from_join_error)?
+    }
+
+    pub(crate) struct ProcessInfosProvider {}
+
+    impl ProcessInfosProvidable for ProcessInfosProvider {
+        async fn get_process_infos(&self) -> Result<ProcessInfos> {
+            crate::linux::tokio::get_process_infos().await
+        }
+    }
+
+    /// Provides the processes from the procfs mounted at `proc_root`, with the process ids of the current pid namespace.  
+    /// Only the target processes and their descendants are provided, or every process if `process_ids` is `None`.  
+    pub(crate) struct ProcfsProcessInfosProvider {
+        proc_root: std::path::PathBuf,
+        process_ids: Option<ProcessIds>,
+    }
+
+    impl ProcessInfosProvidable for ProcfsProcessInfosProvider {
+        async fn get_process_infos(&self) -> Result<ProcessInfos> {
+            let proc_root = self.proc_root.clone();
+            let process_ids = self.process_ids.clone();
+            ::tokio::task::spawn_blocking(move || {
+                super::get_procfs_process_infos(&proc_root, process_ids.as_deref())
+            })
+            .await
+            .map_err(crate::common::tokio::from_join_error)?
+        }
+    }
+
+    pub(crate) fn new_process_infos_provider(config: &Config) -> ProcfsProcessInfosProvider {
+        ProcfsProcessInfosProvider {
+            proc_root: config.proc_root.clone(),
+            process_ids: None,
+        }
+    }
+
+    pub(crate) fn new_tree_process_infos_provider(
+        process_ids: &[ProcessId],
+        config: &Config,
+    ) -> ProcfsProcessInfosProvider {
+        ProcfsProcessInfosProvider {
+            proc_root: config.proc_root.clone(),
+            process_ids: super::get_walk_process_ids(process_ids, config),
+        }
+    }
+
+    /// Waits until the processes exit or the deadline elapses, and pushes their exits in the order they exit.  
+    /// A pidfd becomes readable when its process terminates, so only the processes without a pidfd are polled.  
+    /// Returns `false` without waiting if pidfd is not supported by the kernel (before Linux 5.3).  
+    pub(crate) async fn wait_exits(
+        targets: &[crate::common::ExitTarget],
+        deadline: ::tokio::time::Instant,
+        process_exits: &mut Vec<ProcessExit>,
+    ) -> Result<bool> {
+        let opened_targets = targets.to_vec();
+        let opened =
+            ::tokio::task::spawn_blocking(move || super::open_exit_pidfds(&opened_targets))
+                .await
+                .map_err(crate::common::tokio::from_join_error)?;
+        let super::ExitPidfds {
+            pidfds,
+            gone_process_ids,
+            mut poll_targets,
+        } = match opened {
             Ok(x) => x,
-            Err(e) => {
-                return Err(Error::Io(e));
+            Err(Error::Unix(nix::errno::Errno::ENOSYS)) => {
+                debug!("pidfd is not supported, exits are polled instead");
+                return Ok(false);
             }
+            Err(e) => return Err(e),
         };
-        parse_status(process_id, status_path.display().to_string(), &status)
-    }
-
-    #[instrument]
-    pub(crate) async fn get_process_infos() -> Result<ProcessInfos> {
-        let mut read_dir = ::tokio::fs::read_dir("/proc").await?;
-        let mut process_infos = ProcessInfos::new();
-        while let Some(entry) = read_dir.next_entry().await? {
-            let file_name = entry.file_name();
-            let Some(file_name) = file_name.to_str() else {
-                debug!(file_name = ?file_name, "Failed to convert file name to str");
-                continue;
-            };
-            let process_id = match file_name.parse::<u32>() {
+        process_exits.extend(gone_process_ids.into_iter().map(|process_id| ProcessExit {
+            process_id,
+            exit_outcome: ExitOutcome::Exited,
+        }));
+        let mut waiting_process_ids = pidfds.iter().map(|(x, _)| *x).collect::<Vec<_>>();
+        let mut tasks = ::tokio::task::JoinSet::new();
+        for (process_id, pidfd) in pidfds {
+            let pidfd = match AsyncFd::with_interest(pidfd, Interest::READABLE) {
                 Ok(x) => x,
                 Err(e) => {
-                    debug!(file_name, error = ?e, "Failed to parse process id");
+                    debug!(process_id, error = ?e, "Failed to register pidfd, exit is polled instead");
+                    waiting_process_ids.retain(|x| *x != process_id);
+                    let start_time = targets
+                        .iter()
+                        .find(|(x, _)| *x == process_id)
+                        .and_then(|(_, x)| *x);
+                    poll_targets.push((process_id, start_time));
                     continue;
                 }
             };
-            let process_info = match get_process_info(process_id, entry.path()).await {
-                Ok(x) => x,
-                Err(e) => {
-                    debug!(error = ?e, "Failed to get process info");
-                    continue;
+            tasks.spawn(async move {
+                let _guard = pidfd.readable().await?;
+                // the process has terminated, but may not be reaped yet
+                let exit_outcome = ::tokio::task::spawn_blocking(move || {
+                    super::get_exit_outcome(std::path::Path::new(super::PROC_ROOT), process_id)
+                })
+                .await
+                .map_err(crate::common::tokio::from_join_error)??;
+                let exit_outcome = match exit_outcome {
+                    ExitOutcome::Zombie => ExitOutcome::Zombie,
+                    _ => ExitOutcome::Exited,
+                };
+                Ok::<_, Error>(ProcessExit {
+                    process_id,
+                    exit_outcome,
+                })
+            });
+        }
+        let mut poll_interval = ::tokio::time::interval(crate::common::EXIT_POLL_INTERVAL);
+        while !waiting_process_ids.is_empty() || !poll_targets.is_empty() {
+            let next = ::tokio::time::timeout_at(deadline, async {
+                ::tokio::select! {
+                    Some(result) = tasks.join_next() => Some(result),
+                    _ = poll_interval.tick(), if !poll_targets.is_empty() => None,
                 }
+            });
+            let Ok(result) = next.await else {
+                break;
             };
-            process_infos.push(process_info);
-        }
-        Ok(process_infos)
-    }
-
-    pub(crate) struct ProcessInfosProvider {}
-
-    impl ProcessInfosProvidable for ProcessInfosProvider {
-        async fn get_process_infos(&self) -> Result<ProcessInfos> {
-            crate::linux::tokio::get_process_infos().await
+            let Some(result) = result else {
+                let targets = std::mem::take(&mut poll_targets);
+                let (live_targets, exits) = ::tokio::task::spawn_blocking(move || {
+                    crate::common::tokio::poll_exits(targets)
+                })
+                .await
+                .map_err(crate::common::tokio::from_join_error)??;
+                poll_targets = live_targets;
+                process_exits.extend(exits);
+                continue;
+            };
+            let process_exit = result.map_err(crate::common::tokio::from_join_error)??;
+            waiting_process_ids.retain(|x| *x != process_exit.process_id);
+            process_exits.push(process_exit);
         }
+        process_exits.extend(
+            waiting_process_ids
+                .into_iter()
+                .chain(poll_targets.into_iter().map(|(process_id, _)| process_id))
+                .map(|process_id| ProcessExit {
+                    process_id,
+                    exit_outcome: ExitOutcome::StillAlive,
+                }),
+        );
+        Ok(true)
     }
 }
 
@@ -301,78 +1304,208 @@ mod tests {
     }
 
     #[test]
-    fn parse_status_kernel_process_id() {
-        let process_id = 0;
-        let status_path = "/proc/0/status".to_string();
-        let status = "Name: init\nPPid: 0\n".to_string();
-        let process_info =
-            parse_status(process_id, status_path, &status).expect("Failed to parse status");
-        assert_eq!(process_info.process_id, process_id);
-        assert_eq!(process_info.parent_process_id, 0);
-        assert_eq!(process_info.name, "init");
+    fn get_exe_self() {
+        let exe = get_exe(std::path::Path::new(PROC_ROOT), std::process::id())
+            .expect("Failed to get exe");
+        assert_eq!(
+            exe,
+            std::env::current_exe().expect("Failed to get current exe")
+        );
     }
 
     #[test]
-    fn parse_proc_entry_not_dir() {
-        let process_id = 0;
-        let path = std::path::PathBuf::from("/proc/0/status");
-        let result = parse_proc_entry(process_id, &path);
-        assert!(result.is_err());
+    fn get_cmdline_self() {
+        let cmdline = get_cmdline(std::path::Path::new(PROC_ROOT), std::process::id())
+            .expect("Failed to get cmdline");
+        assert_eq!(cmdline, std::env::args().collect::<Vec<_>>());
     }
 
     #[test]
-    fn parse_proc_entry_failed_to_get_file_name() {
-        let process_id = 0;
-        let path = std::path::PathBuf::from("/proc/");
-        let result = parse_proc_entry(process_id, &path);
-        assert!(result.is_err());
+    fn get_cmdline_available_max_process_id() {
+        assert!(get_cmdline(std::path::Path::new(PROC_ROOT), AVAILABLE_MAX_PROCESS_ID).is_none());
     }
 
     #[test]
-    fn parse_proc_entry_failed_to_parse_process_id() {
-        let process_id = 0;
-        let path = std::path::PathBuf::from("/proc/invalid");
-        let result = parse_proc_entry(process_id, &path);
-        assert!(result.is_err());
+    fn parse_stat_name_with_parentheses() {
+        let process_id = 777;
+        let stat = b"777 (a) (b)) S 1 777 777 0 -1 4194560 100 0 0 0 1 2 0 0 20 0 1 0 12345 1000 100 18446744073709551615 0 0 0 0 0 0 0 0 0 0 0 0 17 0 0 0 0 0 0\n";
+        let stat = parse_stat(process_id, stat).expect("Failed to parse stat");
+        assert_eq!(stat.name, b"a) (b)");
+        assert_eq!(stat.state, b'S');
+        assert_eq!(stat.parent_process_id, 1);
+        assert_eq!(stat.process_group_id, 777);
+        assert_eq!(stat.session_id, 777);
+        assert_eq!(stat.start_time, 12345);
     }
 
     #[test]
-    fn parse_proc_entry_status_path_does_not_exist() {
-        let process_id = 0;
-        let path = std::path::PathBuf::from("/proc/0");
-        let result = parse_proc_entry(process_id, &path);
+    fn parse_stat_name_with_whitespace() {
+        let process_id = 777;
+        let stat = b"777 (Web Content) R 1 2 3 0 -1 0 0 0 0 0 0 0 0 0 20 0 1 0 42 0 0\n";
+        let stat = parse_stat(process_id, stat).expect("Failed to parse stat");
+        assert_eq!(stat.name, b"Web Content");
+        assert_eq!(stat.state, b'R');
+        assert_eq!(stat.start_time, 42);
+    }
+
+    #[test]
+    fn parse_stat_start_time_is_none() {
+        let process_id = 777;
+        let stat = b"777 (a) S 1 777 777\n";
+        let result = parse_stat(process_id, stat);
         assert!(result.is_err());
     }
 
     #[test]
-    fn parse_proc_entry_status_path_is_not_file() {
-        let process_id = 0;
-        let path = std::path::PathBuf::from("/proc/0");
-        let result = parse_proc_entry(process_id, &path);
+    fn parse_stat_name_is_not_closed() {
+        let process_id = 777;
+        let result = parse_stat(process_id, b"777 (a S 1 777 777\n");
+        assert!(result.is_err());
+        let result = parse_stat(process_id, b"777 )a( S 1 777 777\n");
         assert!(result.is_err());
     }
 
     #[test]
-    fn parse_proc_entry_1() {
-        let process_id = 1;
-        let path = std::path::PathBuf::from("/proc/1");
-        let status_path = parse_proc_entry(process_id, &path).expect("Failed to parse proc entry");
-        assert_eq!(status_path, std::path::PathBuf::from("/proc/1/status"));
+    fn parse_proc_entry_name_test() {
+        assert_eq!(parse_proc_entry_name(std::ffi::OsStr::new("1")), Some(1));
+        assert_eq!(
+            parse_proc_entry_name(std::ffi::OsStr::new("4194304")),
+            Some(4_194_304)
+        );
+        assert_eq!(parse_proc_entry_name(std::ffi::OsStr::new("self")), None);
+        assert_eq!(parse_proc_entry_name(std::ffi::OsStr::new("+1")), None);
+        assert_eq!(parse_proc_entry_name(std::ffi::OsStr::new("")), None);
     }
 
     #[test]
-    fn parse_proc_entry_1_and_status() {
-        let process_id = 1;
-        let path = std::path::PathBuf::from("/proc/1");
-        let status_path = parse_proc_entry(process_id, &path).expect("Failed to parse proc entry");
-        let status = match std::fs::read_to_string(&status_path) {
-            Ok(x) => x,
-            Err(e) => {
-                panic!("Failed to read status. error: {e}");
-            }
+    fn stat_reader_self() {
+        use std::os::unix::fs::MetadataExt;
+
+        let process_id = std::process::id();
+        let mut reader = StatReader::default();
+        let process_info = reader
+            .read_process_info(process_id)
+            .expect("Failed to read process info")
+            .expect("Process info is None");
+        assert_eq!(process_info.process_id, process_id);
+        assert_eq!(
+            process_info.parent_process_id,
+            std::os::unix::process::parent_id()
+        );
+        assert!(process_info.state.is_some());
+        let metadata = std::fs::metadata("/proc/self").expect("Failed to get metadata");
+        assert_eq!(process_info.user_id, Some(metadata.uid()));
+        // the buffer is reused for the next read
+        let process_info = reader
+            .read_process_info(1)
+            .expect("Failed to read process info")
+            .expect("Process info is None");
+        assert_eq!(process_info.process_id, 1);
+        assert_eq!(process_info.parent_process_id, 0);
+    }
+
+    #[test]
+    fn stat_reader_no_such_process() {
+        let mut reader = StatReader::default();
+        let result = reader
+            .read_process_info(AVAILABLE_MAX_PROCESS_ID)
+            .expect("Failed to read process info");
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn get_tree_process_infos_child() {
+        let mut child = std::process::Command::new("sleep")
+            .arg("10")
+            .spawn()
+            .expect("Failed to spawn child");
+        let proc_root = std::path::Path::new(PROC_ROOT);
+        let result = get_tree_process_infos(proc_root, &[std::process::id()]);
+        child.kill().expect("Failed to kill child");
+        child.wait().expect("Failed to wait child");
+        let process_infos = result.expect("Failed to get tree process infos");
+        assert!(process_infos
+            .iter()
+            .any(|x| x.process_id == child.id() && x.parent_process_id == std::process::id()));
+        // processes outside the tree are not read
+        assert!(process_infos.iter().all(|x| x.process_id != 1));
+    }
+
+    #[test]
+    fn get_child_process_ids_no_such_process() {
+        let mut buffer = Vec::new();
+        let proc_root = std::path::Path::new(PROC_ROOT);
+        let child_process_ids =
+            get_child_process_ids(proc_root, AVAILABLE_MAX_PROCESS_ID, &mut buffer)
+                .expect("Failed to get child process ids");
+        assert!(child_process_ids.is_empty());
+    }
+
+    #[test]
+    fn get_walk_process_ids_selection() {
+        let config = Config::default();
+        assert_eq!(get_walk_process_ids(&[777], &config), Some(vec![777]));
+        let config = Config {
+            selection: Selection::ProcessGroup,
+            ..Default::default()
         };
-        let process_info = parse_status(process_id, status_path.display().to_string(), &status)
-            .expect("Failed to parse status");
+        assert_eq!(get_walk_process_ids(&[777], &config), None);
+    }
+
+    /// Procfs with hand-written stat and children files, so that the tests do not depend on the processes of the host.  
+    /// 1 `systemd` has 100 `Web Content` and 200 `sshd`, 100 has 101, and 300 has exited before its stat is read.  
+    fn get_fixture_proc_root() -> std::path::PathBuf {
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/proc")
+    }
+
+    #[test]
+    fn get_process_infos_fixture() {
+        let process_infos =
+            get_process_infos(&get_fixture_proc_root()).expect("Failed to get process infos");
+        let mut process_ids = process_infos
+            .iter()
+            .map(|x| x.process_id)
+            .collect::<ProcessIds>();
+        process_ids.sort_unstable();
+        assert_eq!(process_ids, vec![1, 100, 101, 200]);
+        let process_info = process_infos
+            .iter()
+            .find(|x| x.process_id == 101)
+            .expect("Failed to find process 101");
+        assert_eq!(process_info.name, "a) (b)");
+        assert_eq!(process_info.parent_process_id, 100);
+        assert_eq!(process_info.state, Some('Z'));
+        assert_eq!(process_info.start_time, Some(1001));
+    }
+
+    #[test]
+    fn get_tree_process_infos_fixture() {
+        let process_infos = get_tree_process_infos(&get_fixture_proc_root(), &[100])
+            .expect("Failed to get tree process infos");
+        let process_ids = process_infos
+            .iter()
+            .map(|x| x.process_id)
+            .collect::<ProcessIds>();
+        assert_eq!(process_ids, vec![100, 101]);
+        assert_eq!(process_infos[0].name, "Web Content");
+    }
+
+    #[test]
+    fn get_child_process_ids_fixture() {
+        let mut buffer = Vec::new();
+        let child_process_ids = get_child_process_ids(&get_fixture_proc_root(), 1, &mut buffer)
+            .expect("Failed to get child process ids");
+        assert_eq!(child_process_ids, vec![100, 200]);
+    }
+
+    #[test]
+    fn read_process_info_1() {
+        let process_id = 1;
+        let proc_root = get_fixture_proc_root();
+        let process_info = StatReader::new(&proc_root)
+            .read_process_info(process_id)
+            .expect("Failed to get process info")
+            .expect("Process info is None");
         assert_eq!(process_info.process_id, process_id);
         assert_eq!(process_info.parent_process_id, 0);
         assert_eq!(process_info.name, "systemd");
@@ -384,20 +1517,160 @@ mod tests {
             process_id: 0,
             parent_process_id: 0,
             name: "init".to_string(),
+            ..Default::default()
         };
         assert!(!child_process_id_map_filter(&process_info));
     }
 
-    #[cfg(feature = "blocking")]
     #[test]
-    fn get_process_info_blocking() {
-        let process_id = 1;
-        let path = std::path::PathBuf::from("/proc/1");
-        let process_info =
-            blocking::get_process_info(process_id, path).expect("Failed to get process info");
-        assert_eq!(process_info.process_id, process_id);
-        assert_eq!(process_info.parent_process_id, 0);
-        assert_eq!(process_info.name, "systemd");
+    fn get_exit_outcome_self() {
+        let exit_outcome = get_exit_outcome(std::path::Path::new(PROC_ROOT), std::process::id())
+            .expect("Failed to get exit outcome");
+        assert_eq!(exit_outcome, ExitOutcome::StillAlive);
+    }
+
+    #[test]
+    fn get_exit_outcome_available_max_process_id() {
+        let exit_outcome =
+            get_exit_outcome(std::path::Path::new(PROC_ROOT), AVAILABLE_MAX_PROCESS_ID)
+                .expect("Failed to get exit outcome");
+        assert_eq!(exit_outcome, ExitOutcome::Exited);
+    }
+
+    #[test]
+    fn get_start_time_self() {
+        let start_time = get_start_time(std::path::Path::new(PROC_ROOT), std::process::id())
+            .expect("Failed to get start time");
+        assert!(start_time.is_some());
+    }
+
+    #[test]
+    fn get_start_time_available_max_process_id() {
+        let start_time = get_start_time(std::path::Path::new(PROC_ROOT), AVAILABLE_MAX_PROCESS_ID)
+            .expect("Failed to get start time");
+        assert!(start_time.is_none());
+    }
+
+    #[test]
+    fn parse_cgroup_v2() {
+        let cgroup = "0::/user.slice/user-1000.slice/session-1.scope\n";
+        assert_eq!(
+            parse_cgroup(cgroup),
+            Some("/user.slice/user-1000.slice/session-1.scope")
+        );
+    }
+
+    #[test]
+    fn parse_cgroup_v1() {
+        let cgroup = "12:pids:/user.slice\n1:name=systemd:/user.slice\n";
+        assert_eq!(parse_cgroup(cgroup), None);
+    }
+
+    /// Creates a fake cgroup tree with `cgroup.procs` and `cgroup.kill` files.
+    fn create_fake_cgroup(name: &str, procs: &str, child_procs: &str) -> std::path::PathBuf {
+        let cgroup_path =
+            std::env::temp_dir().join(format!("kill_tree_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&cgroup_path);
+        std::fs::create_dir_all(cgroup_path.join("child")).unwrap();
+        std::fs::write(cgroup_path.join("cgroup.procs"), procs).unwrap();
+        std::fs::write(cgroup_path.join("cgroup.kill"), "").unwrap();
+        std::fs::write(cgroup_path.join("child/cgroup.procs"), child_procs).unwrap();
+        std::fs::write(cgroup_path.join("child/cgroup.kill"), "").unwrap();
+        cgroup_path
+    }
+
+    #[test]
+    fn get_cgroup_process_ids_fake() {
+        let cgroup_path = create_fake_cgroup("get_cgroup_process_ids", "3\n1\n", "2\n");
+        let process_ids = get_cgroup_process_ids(&cgroup_path).expect("Failed to get process ids");
+        assert_eq!(process_ids, vec![1, 2, 3]);
+        let _ = std::fs::remove_dir_all(&cgroup_path);
+    }
+
+    #[test]
+    fn get_cgroup_path_to_kill_fake() {
+        let cgroup_root = std::env::temp_dir().join("kill_tree_no_such_cgroup_root");
+        let process_id = std::process::id();
+        // the cgroup of the current process does not exist under the fake root
+        assert!(get_cgroup_path_to_kill(
+            std::path::Path::new(PROC_ROOT),
+            &cgroup_root,
+            process_id,
+            &[process_id],
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn kill_cgroup_fake() {
+        let cgroup_path =
+            create_fake_cgroup("kill_cgroup", &format!("{AVAILABLE_MAX_PROCESS_ID}\n"), "");
+        let process_ids = get_cgroup_process_ids(&cgroup_path).expect("Failed to get process ids");
+        let outputs = kill_cgroup(&cgroup_path, process_ids, ProcessInfos::new())
+            .expect("Failed to kill cgroup");
+        assert_eq!(outputs.len(), 1);
+        assert!(matches!(
+            outputs[0],
+            Output::MaybeAlreadyTerminated {
+                process_id: AVAILABLE_MAX_PROCESS_ID,
+                ..
+            }
+        ));
+        let cgroup_kill = std::fs::read_to_string(cgroup_path.join("cgroup.kill")).unwrap();
+        assert_eq!(cgroup_kill, "1");
+        let _ = std::fs::remove_dir_all(&cgroup_path);
+    }
+
+    #[test]
+    fn write_cgroup_kill_not_found() {
+        let cgroup_path = std::env::temp_dir().join("kill_tree_no_such_cgroup");
+        assert!(write_cgroup_kill(&cgroup_path).is_err());
+    }
+
+    #[test]
+    fn pidfd_open_self() {
+        pidfd_open(std::process::id()).expect("Failed to open pidfd");
+    }
+
+    #[test]
+    fn pidfd_open_available_max_process_id() {
+        let result = pidfd_open(AVAILABLE_MAX_PROCESS_ID);
+        assert!(matches!(result, Err(Error::Unix(nix::errno::Errno::ESRCH))));
+    }
+
+    #[test]
+    fn killer_pidfd_self() {
+        let killer_builder = KillerBuilder::new(&[std::process::id()]);
+        assert!(matches!(
+            killer_builder.pidfds.get(&std::process::id()),
+            Some(Pidfd::Opened(_))
+        ));
+        // SIGCONT does nothing to a running process.
+        let killer = killer_builder
+            .new_killable(Signal::SIGCONT)
+            .expect("Failed to create killer");
+        let kill_output = killer.kill(std::process::id()).expect("Failed to kill");
+        assert!(matches!(kill_output, KillOutput::Killed { .. }));
+    }
+
+    #[test]
+    fn killer_pidfd_gone() {
+        let killer_builder = KillerBuilder::new(&[AVAILABLE_MAX_PROCESS_ID]);
+        let killer = killer_builder
+            .new_killable(Signal::SIGCONT)
+            .expect("Failed to create killer");
+        let kill_output = killer
+            .kill(AVAILABLE_MAX_PROCESS_ID)
+            .expect("Failed to kill");
+        match kill_output {
+            KillOutput::MaybeAlreadyTerminated { process_id, source } => {
+                assert_eq!(process_id, AVAILABLE_MAX_PROCESS_ID);
+                assert_eq!(source.to_string(), "Unix error: ESRCH: No such process");
+            }
+            KillOutput::Killed { .. } | KillOutput::Failed { .. } => {
+                panic!("This should not happen");
+            }
+        }
     }
 
     #[cfg(feature = "blocking")]
@@ -411,14 +1684,60 @@ mod tests {
     #[test]
     fn get_process_info_tokio() {
         let rt = ::tokio::runtime::Runtime::new().unwrap();
-        let process_id = 1;
-        let path = std::path::PathBuf::from("/proc/1");
-        let process_info = rt
-            .block_on(tokio::get_process_info(process_id, path))
-            .expect("Failed to get process info");
-        assert_eq!(process_info.process_id, process_id);
-        assert_eq!(process_info.parent_process_id, 0);
-        assert_eq!(process_info.name, "systemd");
+        let process_infos = rt
+            .block_on(tokio::get_process_infos())
+            .expect("Failed to get process infos");
+        let process_info = process_infos
+            .iter()
+            .find(|x| x.process_id == std::process::id())
+            .expect("Failed to find the current process");
+        assert_eq!(
+            process_info.parent_process_id,
+            std::os::unix::process::parent_id()
+        );
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn procfs_process_infos_provider_blocking() {
+        use crate::core::blocking::ProcessInfosProvidable;
+
+        let config = Config::builder()
+            .proc_root(get_fixture_proc_root())
+            .build()
+            .expect("Failed to build config");
+        let process_infos = blocking::new_process_infos_provider(&config)
+            .get_process_infos()
+            .expect("Failed to get process infos");
+        assert_eq!(process_infos.len(), 4);
+        let process_infos = blocking::new_tree_process_infos_provider(&[1], &config)
+            .get_process_infos()
+            .expect("Failed to get process infos");
+        let process_ids = process_infos
+            .iter()
+            .map(|x| x.process_id)
+            .collect::<ProcessIds>();
+        assert_eq!(process_ids, vec![1, 100, 200, 101]);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn procfs_process_infos_provider_tokio() {
+        use crate::core::tokio::ProcessInfosProvidable;
+
+        let rt = ::tokio::runtime::Runtime::new().unwrap();
+        let config = Config::builder()
+            .proc_root(get_fixture_proc_root())
+            .build()
+            .expect("Failed to build config");
+        let process_infos = rt
+            .block_on(tokio::new_tree_process_infos_provider(&[100], &config).get_process_infos())
+            .expect("Failed to get process infos");
+        let process_ids = process_infos
+            .iter()
+            .map(|x| x.process_id)
+            .collect::<ProcessIds>();
+        assert_eq!(process_ids, vec![100, 101]);
     }
 
     #[cfg(feature = "tokio")]
@@ -430,4 +1749,261 @@ mod tests {
             .expect("Failed to get process infos");
         assert!(process_infos.len() > 1);
     }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn open_exit_pidfds_poll_targets() {
+        // pidfd_open fails with EINVAL for the process id 0, as it fails with EMFILE over the open files limit
+        let process_id = std::process::id();
+        let exit_pidfds =
+            open_exit_pidfds(&[(0, None), (process_id, None)]).expect("Failed to open exit pidfds");
+        assert_eq!(exit_pidfds.poll_targets, vec![(0, None)]);
+        let process_ids = exit_pidfds
+            .pidfds
+            .iter()
+            .map(|(x, _)| *x)
+            .collect::<ProcessIds>();
+        assert_eq!(process_ids, vec![process_id]);
+        assert!(exit_pidfds.gone_process_ids.is_empty());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn wait_exits_poll_targets() {
+        use crate::core::ProcessExit;
+
+        let rt = ::tokio::runtime::Runtime::new().unwrap();
+        let mut process_exits = Vec::new();
+        let waited = rt
+            .block_on(async {
+                let deadline =
+                    ::tokio::time::Instant::now() + std::time::Duration::from_millis(100);
+                // the process id 0 is polled, and the current process is waited on with its pidfd
+                tokio::wait_exits(
+                    &[(0, None), (std::process::id(), None)],
+                    deadline,
+                    &mut process_exits,
+                )
+                .await
+            })
+            .expect("Failed to wait exits");
+        assert!(waited);
+        assert_eq!(
+            process_exits,
+            vec![
+                ProcessExit {
+                    process_id: 0,
+                    exit_outcome: ExitOutcome::Exited,
+                },
+                ProcessExit {
+                    process_id: std::process::id(),
+                    exit_outcome: ExitOutcome::StillAlive,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_namespace_process_ids_status() {
+        let status =
+            "Name:\tsh\nNStgid:\t5000\t7\nNSpid:\t5000\t7\nNSpgid:\t4000\t1\nNSsid:\t4000\t1\n";
+        assert_eq!(
+            parse_namespace_process_ids(status),
+            NamespaceProcessIds {
+                process_ids: Some(vec![5000, 7]),
+                process_group_ids: Some(vec![4000, 1]),
+            }
+        );
+        // before Linux 4.1
+        assert_eq!(
+            parse_namespace_process_ids("Name:\tsh\nPid:\t5000\n"),
+            NamespaceProcessIds::default()
+        );
+    }
+
+    #[test]
+    fn get_namespace_process_ids_self() {
+        let process_id = std::process::id();
+        let (process_ids, process_group_ids) =
+            get_namespace_process_ids(std::path::Path::new(PROC_ROOT), process_id);
+        assert_eq!(
+            process_ids.and_then(|x| x.first().copied()),
+            Some(process_id)
+        );
+        assert!(process_group_ids.is_some());
+    }
+
+    #[test]
+    fn get_pid_namespace_current() {
+        assert_eq!(
+            get_pid_namespace(std::path::Path::new(PROC_ROOT))
+                .expect("Failed to get pid namespace"),
+            PidNamespace::Current
+        );
+    }
+
+    #[test]
+    fn get_pid_namespace_unknown() {
+        // no self entry
+        assert_eq!(
+            get_pid_namespace(&get_fixture_proc_root()).expect("Failed to get pid namespace"),
+            PidNamespace::Unknown
+        );
+    }
+
+    #[test]
+    fn get_signal_proc_root_fixture() {
+        let proc_root = get_fixture_proc_root();
+        assert_eq!(
+            get_read_proc_root(&proc_root).expect("Failed to get proc root"),
+            proc_root
+        );
+        assert!(matches!(
+            get_signal_proc_root(&proc_root),
+            Err(Error::Io(e)) if e.kind() == std::io::ErrorKind::InvalidInput
+        ));
+    }
+
+    #[test]
+    fn get_cmdline_fixture() {
+        let proc_root = get_fixture_proc_root();
+        assert_eq!(
+            get_cmdline(&proc_root, 100),
+            Some(vec!["firefox".to_string(), "-contentproc".to_string()])
+        );
+        assert_eq!(
+            get_start_time(&proc_root, 100).expect("Failed to get start time"),
+            Some(1000)
+        );
+    }
+
+    /// Writes a process in the procfs like directory, with `NSpid` and `NSpgid` of nested pid namespaces.  
+    fn write_namespace_process(
+        proc_root: &std::path::Path,
+        namespace_process_ids: &[ProcessId],
+        namespace_process_group_ids: &[ProcessId],
+        start_time: u64,
+    ) {
+        let join = |x: &[ProcessId]| {
+            x.iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\t")
+        };
+        let process_id = namespace_process_ids[0];
+        let process_dir = proc_root.join(process_id.to_string());
+        std::fs::create_dir_all(&process_dir).expect("Failed to create process dir");
+        let stat = format!(
+            "{process_id} (sh) S 1 {} {} 0 -1 0 0 0 0 0 0 0 0 0 20 0 1 0 {start_time} 0 0\n",
+            namespace_process_group_ids[0], namespace_process_group_ids[0]
+        );
+        std::fs::write(process_dir.join("stat"), stat).expect("Failed to write stat");
+        let status = format!(
+            "Name:\tsh\nNSpid:\t{}\nNSpgid:\t{}\n",
+            join(namespace_process_ids),
+            join(namespace_process_group_ids)
+        );
+        std::fs::write(process_dir.join("status"), status).expect("Failed to write status");
+    }
+
+    /// Creates an empty procfs like directory in the temporary directory, unique for the test.  
+    fn create_temp_proc_root(name: &str) -> std::path::PathBuf {
+        let proc_root =
+            std::env::temp_dir().join(format!("kill_tree_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&proc_root);
+        std::fs::create_dir_all(&proc_root).expect("Failed to create proc root");
+        proc_root
+    }
+
+    #[test]
+    fn pid_namespace_ancestor() {
+        let process_id = std::process::id();
+        let process_info = StatReader::default()
+            .read_process_info(process_id)
+            .expect("Failed to read process info")
+            .expect("Process info is None");
+        let start_time = process_info.start_time.expect("Start time is None");
+        let process_group_id = process_info
+            .process_group_id
+            .expect("Process group id is None");
+        // the procfs of the parent pid namespace, where the current process is 5000
+        let proc_root = create_temp_proc_root("pid_namespace_ancestor");
+        std::os::unix::fs::symlink("5000", proc_root.join("self")).expect("Failed to link self");
+        write_namespace_process(
+            &proc_root,
+            &[5000, process_id],
+            &[5000, process_group_id],
+            start_time,
+        );
+        // only in the parent pid namespace
+        write_namespace_process(&proc_root, &[4000], &[4000], 1);
+        // the process id is reused by another process
+        write_namespace_process(
+            &proc_root,
+            &[6000, process_id],
+            &[6000, process_group_id],
+            1,
+        );
+
+        assert_eq!(
+            get_pid_namespace(&proc_root).expect("Failed to get pid namespace"),
+            PidNamespace::Ancestor { level: 1 }
+        );
+        let process_infos =
+            get_procfs_process_infos(&proc_root, None).expect("Failed to get process infos");
+        assert_eq!(process_infos.len(), 1);
+        assert_eq!(process_infos[0].process_id, process_id);
+        assert_eq!(
+            process_infos[0].parent_process_id,
+            process_info.parent_process_id
+        );
+        assert_eq!(
+            process_infos[0].namespace_process_ids,
+            Some(vec![5000, process_id])
+        );
+        assert_eq!(
+            process_infos[0].namespace_process_group_ids,
+            Some(vec![5000, process_group_id])
+        );
+        let config = Config {
+            proc_root: proc_root.clone(),
+            ..Default::default()
+        };
+        assert_eq!(
+            get_caller_process_id(5000, &config).expect("Failed to get caller process id"),
+            process_id
+        );
+        for invisible_process_id in [4000, 6000, 7000] {
+            let result = get_caller_process_id(invisible_process_id, &config);
+            assert!(
+                matches!(result, Err(Error::InvalidProcessId { process_id, .. }) if process_id == invisible_process_id)
+            );
+        }
+        std::fs::remove_dir_all(&proc_root).expect("Failed to remove proc root");
+    }
+
+    #[test]
+    fn pid_namespace_descendant() {
+        // the procfs of a nested pid namespace, where the current process is not visible
+        let proc_root = create_temp_proc_root("pid_namespace_descendant");
+        std::os::unix::fs::symlink("999999", proc_root.join("self")).expect("Failed to link self");
+        write_namespace_process(&proc_root, &[7], &[7], 1);
+
+        assert_eq!(
+            get_pid_namespace(&proc_root).expect("Failed to get pid namespace"),
+            PidNamespace::Descendant
+        );
+        let process_infos =
+            get_procfs_process_infos(&proc_root, None).expect("Failed to get process infos");
+        assert!(process_infos.is_empty());
+        let config = Config {
+            proc_root: proc_root.clone(),
+            ..Default::default()
+        };
+        assert!(matches!(
+            get_caller_process_id(7, &config),
+            Err(Error::InvalidProcessId { process_id: 7, .. })
+        ));
+        std::fs::remove_dir_all(&proc_root).expect("Failed to remove proc root");
+    }
 }
@@ -1,6 +1,7 @@
 use crate::{
     core::{
-        Config, Error, KillableBuildable, ProcessId, ProcessIds, ProcessInfo, ProcessInfos, Result,
+        Config, Error, ExitOutcome, KillableBuildable, ProcessId, ProcessIds, ProcessInfo,
+        ProcessInfos, Result, Signal,
     },
     unix::Killer,
 };
@@ -17,8 +18,14 @@ pub(crate) fn child_process_id_map_filter(_process_info: &ProcessInfo) -> bool {
     false
 }
 
-#[instrument]
-pub(crate) fn get_process_info(process_id: ProcessId) -> Result<ProcessInfo> {
+/// Zombie state of `pbi_status`. See `sys/proc.h`.  
+const SIDL: u32 = 1;
+const SRUN: u32 = 2;
+const SSLEEP: u32 = 3;
+const SSTOP: u32 = 4;
+const SZOMB: u32 = 5;
+
+fn get_proc_bsdinfo(process_id: ProcessId) -> Result<libproc::proc_bsdinfo> {
     let proc_bsdinfo_size = match u32::try_from(std::mem::size_of::<libproc::proc_bsdinfo>()) {
         Ok(x) => x,
         Err(e) => {
@@ -68,6 +75,12 @@ pub(crate) fn get_process_info(process_id: ProcessId) -> Result<ProcessInfo> {
     if result <= 0 {
         return Err(std::io::Error::last_os_error().into());
     }
+    Ok(proc_bsdinfo)
+}
+
+#[instrument]
+pub(crate) fn get_process_info(process_id: ProcessId) -> Result<ProcessInfo> {
+    let proc_bsdinfo = get_proc_bsdinfo(process_id)?;
     let name = unsafe { std::ffi::CStr::from_ptr(std::ptr::addr_of!(proc_bsdinfo.pbi_name[0])) }
         .to_string_lossy()
         .to_string();
@@ -75,9 +88,187 @@ pub(crate) fn get_process_info(process_id: ProcessId) -> Result<ProcessInfo> {
         process_id,
         parent_process_id: proc_bsdinfo.pbi_ppid,
         name,
+        process_group_id: Some(proc_bsdinfo.pbi_pgid),
+        session_id: get_session_id(process_id),
+        start_time: Some(get_start_time_micros(&proc_bsdinfo)),
+        user_id: Some(proc_bsdinfo.pbi_uid),
+        group_id: Some(proc_bsdinfo.pbi_gid),
+        state: get_state(proc_bsdinfo.pbi_status),
+        cmdline: None,
+        exe: None,
+        namespace_process_ids: None,
+        namespace_process_group_ids: None,
     })
 }
 
+/// Returns the state as the letter used by `ps`.
+fn get_state(status: u32) -> Option<char> {
+    match status {
+        SIDL => Some('I'),
+        SRUN => Some('R'),
+        SSLEEP => Some('S'),
+        SSTOP => Some('T'),
+        SZOMB => Some('Z'),
+        _ => None,
+    }
+}
+
+/// `None` if it is not allowed to inquire.
+fn get_session_id(process_id: ProcessId) -> Option<ProcessId> {
+    let process_id_sign = i32::try_from(process_id).ok()?;
+    match nix::unistd::getsid(Some(nix::unistd::Pid::from_raw(process_id_sign))) {
+        Ok(x) => u32::try_from(x.as_raw()).ok(),
+        Err(e) => {
+            debug!(process_id, error = ?e, "Failed to get session id");
+            None
+        }
+    }
+}
+
+/// Microseconds since the epoch.
+fn get_start_time_micros(proc_bsdinfo: &libproc::proc_bsdinfo) -> u64 {
+    proc_bsdinfo
+        .pbi_start_tvsec
+        .saturating_mul(1_000_000)
+        .saturating_add(proc_bsdinfo.pbi_start_tvusec)
+}
+
+/// Returns the start time of the process that has the process id now.  
+/// `None` if there is no such process, or it is not allowed to inquire.  
+pub(crate) fn get_start_time(
+    _proc_root: &std::path::Path,
+    process_id: ProcessId,
+) -> Result<Option<u64>> {
+    match get_proc_bsdinfo(process_id) {
+        Ok(proc_bsdinfo) => Ok(Some(get_start_time_micros(&proc_bsdinfo))),
+        Err(e) => {
+            debug!(process_id, error = ?e, "Failed to get proc bsdinfo");
+            Ok(None)
+        }
+    }
+}
+
+/// Returns the `KERN_PROCARGS2` of the process.  
+/// It has argc, the executable path, NUL paddings, and then the arguments terminated by NUL.  
+/// `None` if there is no such process, or it is not allowed to inquire.  
+fn get_procargs(process_id: ProcessId) -> Option<Vec<u8>> {
+    let process_id_sign = i32::try_from(process_id).ok()?;
+    let mut mib = [
+        nix::libc::CTL_KERN,
+        nix::libc::KERN_PROCARGS2,
+        process_id_sign,
+    ];
+    let mut size: nix::libc::size_t = 0;
+    let result = unsafe {
+        nix::libc::sysctl(
+            mib.as_mut_ptr(),
+            3,
+            std::ptr::null_mut(),
+            &mut size,
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+    if result != 0 {
+        debug!(process_id, error = ?std::io::Error::last_os_error(), "Failed to get size of procargs");
+        return None;
+    }
+    let mut buffer = vec![0_u8; size];
+    let result = unsafe {
+        nix::libc::sysctl(
+            mib.as_mut_ptr(),
+            3,
+            buffer.as_mut_ptr().cast(),
+            &mut size,
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+    if result != 0 {
+        debug!(process_id, error = ?std::io::Error::last_os_error(), "Failed to get procargs");
+        return None;
+    }
+    buffer.truncate(size);
+    Some(buffer)
+}
+
+/// Returns the command line arguments of the process from `KERN_PROCARGS2`.  
+/// `None` if there is no such process, or it is not allowed to inquire.  
+pub(crate) fn get_cmdline(
+    _proc_root: &std::path::Path,
+    process_id: ProcessId,
+) -> Option<Vec<String>> {
+    let buffer = get_procargs(process_id)?;
+    let argc = i32::from_ne_bytes(buffer.get(..4)?.try_into().ok()?);
+    let argc = usize::try_from(argc).ok()?;
+    let rest = buffer.get(4..)?;
+    let executable_path_end = rest.iter().position(|x| *x == 0)?;
+    let rest = &rest[executable_path_end..];
+    let arguments_start = rest.iter().position(|x| *x != 0)?;
+    Some(
+        rest[arguments_start..]
+            .split(|x| *x == 0)
+            .take(argc)
+            .map(|x| String::from_utf8_lossy(x).into_owned())
+            .collect(),
+    )
+}
+
+/// Pid namespaces are Linux only, so the process id is used as is.
+pub(crate) fn get_caller_process_id(process_id: ProcessId, _config: &Config) -> Result<ProcessId> {
+    Ok(process_id)
+}
+
+/// Procfs is Linux only, so the processes are read as is.
+pub(crate) fn get_read_proc_root(proc_root: &std::path::Path) -> Result<&std::path::Path> {
+    Ok(proc_root)
+}
+
+/// Procfs is Linux only, so the processes are signalled as is.
+pub(crate) fn get_signal_proc_root(proc_root: &std::path::Path) -> Result<&std::path::Path> {
+    Ok(proc_root)
+}
+
+/// Pid namespaces are Linux only.
+pub(crate) fn get_namespace_process_ids(
+    _proc_root: &std::path::Path,
+    _process_id: ProcessId,
+) -> (Option<ProcessIds>, Option<ProcessIds>) {
+    (None, None)
+}
+
+/// Returns the path of the executable of the process from `KERN_PROCARGS2`.  
+/// `None` if there is no such process, or it is not allowed to inquire.  
+pub(crate) fn get_exe(
+    _proc_root: &std::path::Path,
+    process_id: ProcessId,
+) -> Option<std::path::PathBuf> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let buffer = get_procargs(process_id)?;
+    let rest = buffer.get(4..)?;
+    let executable_path_end = rest.iter().position(|x| *x == 0)?;
+    Some(std::path::PathBuf::from(std::ffi::OsStr::from_bytes(
+        &rest[..executable_path_end],
+    )))
+}
+
+/// Returns whether the process has exited, has become a zombie or is still alive.  
+pub(crate) fn get_exit_outcome(
+    _proc_root: &std::path::Path,
+    process_id: ProcessId,
+) -> Result<ExitOutcome> {
+    match get_proc_bsdinfo(process_id) {
+        Ok(proc_bsdinfo) if proc_bsdinfo.pbi_status == SZOMB => Ok(ExitOutcome::Zombie),
+        Ok(_) => Ok(ExitOutcome::StillAlive),
+        Err(e) => {
+            // e.g. processes of other users are not allowed to inquire.
+            debug!(process_id, error = ?e, "Failed to get proc bsdinfo");
+            crate::unix::get_exit_outcome(process_id)
+        }
+    }
+}
+
 #[instrument]
 pub(crate) fn get_process_ids() -> Result<ProcessIds> {
     let buffer_size_sign =
@@ -127,22 +318,48 @@ pub(crate) fn get_process_infos() -> Result<ProcessInfos> {
     Ok(process_infos)
 }
 
+pub(crate) fn parse_signal_name(name: &str) -> Option<i32> {
+    crate::unix::parse_signal_name(name)
+}
+
+pub(crate) fn get_signal_name(number: i32) -> Option<String> {
+    crate::unix::get_signal_name(number)
+}
+
 pub(crate) struct KillerBuilder {}
 
+impl KillerBuilder {
+    pub(crate) fn new(_process_ids: &[ProcessId]) -> Self {
+        Self {}
+    }
+}
+
 impl KillableBuildable for KillerBuilder {
-    fn new_killable(&self, config: &Config) -> Result<Killer> {
+    fn new_killable(&self, signal: Signal) -> Result<Killer> {
         let killer_builder = crate::unix::KillerBuilder {};
-        killer_builder.new_killable(config)
+        killer_builder.new_killable(signal)
     }
 }
 
 #[cfg(feature = "blocking")]
 pub(crate) mod blocking {
     use super::{ProcessInfos, Result};
-    use crate::core::blocking::ProcessInfosProvidable;
+    use crate::core::{blocking::ProcessInfosProvidable, Config, ProcessId};
 
     pub(crate) struct ProcessInfosProvider {}
 
+    pub(crate) fn new_process_infos_provider(_config: &Config) -> ProcessInfosProvider {
+        ProcessInfosProvider {}
+    }
+
+    /// Provides every process, since the children of a process cannot be listed directly.
+    pub(crate) fn new_tree_process_infos_provider(
+        _process_ids: &[ProcessId],
+        _config: &Config,
+    ) -> ProcessInfosProvider {
+        ProcessInfosProvider {}
+    }
+
     impl ProcessInfosProvidable for ProcessInfosProvider {
         fn get_process_infos(&self) -> Result<ProcessInfos> {
             crate::macos::get_process_infos()
@@ -153,10 +370,22 @@ pub(crate) mod blocking {
 #[cfg(feature = "tokio")]
 pub(crate) mod tokio {
     use super::{ProcessInfos, Result};
-    use crate::core::tokio::ProcessInfosProvidable;
+    use crate::core::{tokio::ProcessInfosProvidable, Config, ProcessId};
 
     pub(crate) struct ProcessInfosProvider {}
 
+    pub(crate) fn new_process_infos_provider(_config: &Config) -> ProcessInfosProvider {
+        ProcessInfosProvider {}
+    }
+
+    /// Provides every process, since the children of a process cannot be listed directly.
+    pub(crate) fn new_tree_process_infos_provider(
+        _process_ids: &[ProcessId],
+        _config: &Config,
+    ) -> ProcessInfosProvider {
+        ProcessInfosProvider {}
+    }
+
     impl ProcessInfosProvidable for ProcessInfosProvider {
         async fn get_process_infos(&self) -> Result<ProcessInfos> {
             crate::macos::get_process_infos()
@@ -202,6 +431,7 @@ mod tests {
             process_id: 0,
             parent_process_id: 0,
             name: "name".to_string(),
+            ..Default::default()
         };
         assert!(!child_process_id_map_filter(&process_info));
     }
@@ -235,6 +465,38 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn get_cmdline_self() {
+        let cmdline = get_cmdline(std::path::Path::new("/proc"), std::process::id())
+            .expect("Failed to get cmdline");
+        assert_eq!(cmdline, std::env::args().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn get_exe_self() {
+        let exe =
+            get_exe(std::path::Path::new("/proc"), std::process::id()).expect("Failed to get exe");
+        assert_eq!(
+            exe,
+            std::env::current_exe().expect("Failed to get current exe")
+        );
+    }
+
+    #[test]
+    fn get_exit_outcome_self() {
+        let exit_outcome = get_exit_outcome(std::path::Path::new("/proc"), std::process::id())
+            .expect("Failed to get exit outcome");
+        assert_eq!(exit_outcome, ExitOutcome::StillAlive);
+    }
+
+    #[test]
+    fn get_exit_outcome_available_max_process_id() {
+        let exit_outcome =
+            get_exit_outcome(std::path::Path::new("/proc"), AVAILABLE_MAX_PROCESS_ID)
+                .expect("Failed to get exit outcome");
+        assert_eq!(exit_outcome, ExitOutcome::Exited);
+    }
+
     #[test]
     fn get_process_ids_test() {
         let process_ids = get_process_ids().expect("Failed to get process ids");
@@ -1,6 +1,6 @@
 use crate::{
-    core::{KillOutput, Killable, KillableBuildable, ProcessId, Result},
-    Config, Error,
+    core::{ExitOutcome, KillOutput, Killable, KillableBuildable, ProcessId, Result, Signal},
+    Error,
 };
 use tracing::instrument;
 
@@ -32,13 +32,31 @@ pub(crate) fn validate_process_id(process_id: ProcessId, available_max: ProcessI
     }
 }
 
+/// Returns the number of the signal, e.g. `TERM`, without the `SIG` prefix.
+pub(crate) fn parse_signal_name(name: &str) -> Option<i32> {
+    format!("SIG{name}")
+        .parse::<nix::sys::signal::Signal>()
+        .ok()
+        .map(|x| x as i32)
+}
+
+/// `None` if the signal number is not supported.
+pub(crate) fn get_signal_name(number: i32) -> Option<String> {
+    nix::sys::signal::Signal::try_from(number)
+        .ok()
+        .map(|x| x.as_str().to_string())
+}
+
 #[instrument]
-pub(crate) fn kill(process_id: ProcessId, signal: nix::sys::signal::Signal) -> Result<KillOutput> {
+pub(crate) fn kill(process_id: ProcessId, signal: Signal) -> Result<KillOutput> {
     let process_id_sign = i32::try_from(process_id).map_err(|e| Error::InvalidCast {
         reason: "Failed to cast process id to i32".into(),
         source: e,
     })?;
-    let result = nix::sys::signal::kill(nix::unistd::Pid::from_raw(process_id_sign), signal);
+    // real-time signals are not supported by `nix::sys::signal::kill`
+    let result =
+        nix::errno::Errno::result(unsafe { nix::libc::kill(process_id_sign, signal.number()) })
+            .map(drop);
     match result {
         Ok(()) => Ok(KillOutput::Killed { process_id }),
         Err(e) => {
@@ -57,9 +75,24 @@ pub(crate) fn kill(process_id: ProcessId, signal: nix::sys::signal::Signal) -> R
     }
 }
 
+/// Returns whether the process has exited or is still alive.  
+/// Zombie processes are reported as still alive.  
+pub(crate) fn get_exit_outcome(process_id: ProcessId) -> Result<ExitOutcome> {
+    let process_id_sign = i32::try_from(process_id).map_err(|e| Error::InvalidCast {
+        reason: "Failed to cast process id to i32".into(),
+        source: e,
+    })?;
+    match nix::sys::signal::kill(nix::unistd::Pid::from_raw(process_id_sign), None) {
+        // EPERM: the process exists, but is not allowed to be signalled.
+        Ok(()) | Err(nix::errno::Errno::EPERM) => Ok(ExitOutcome::StillAlive),
+        Err(nix::errno::Errno::ESRCH) => Ok(ExitOutcome::Exited),
+        Err(e) => Err(e.into()),
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct Killer {
-    signal: nix::sys::signal::Signal,
+    pub(crate) signal: Signal,
 }
 
 impl Killable for Killer {
@@ -71,8 +104,7 @@ impl Killable for Killer {
 pub(crate) struct KillerBuilder {}
 
 impl KillableBuildable for KillerBuilder {
-    fn new_killable(&self, config: &Config) -> Result<Killer> {
-        let signal = config.signal.parse()?;
+    fn new_killable(&self, signal: Signal) -> Result<Killer> {
         Ok(Killer { signal })
     }
 }
@@ -118,13 +150,26 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn get_exit_outcome_self() {
+        let exit_outcome =
+            get_exit_outcome(std::process::id()).expect("Failed to get exit outcome");
+        assert_eq!(exit_outcome, ExitOutcome::StillAlive);
+    }
+
+    #[test]
+    fn get_exit_outcome_available_max_process_id() {
+        let exit_outcome =
+            get_exit_outcome(get_available_max_process_id()).expect("Failed to get exit outcome");
+        assert_eq!(exit_outcome, ExitOutcome::Exited);
+    }
+
     #[test]
     fn kii_sigterm() {
         let target_process_id = get_available_max_process_id();
-        let kill_output =
-            kill(target_process_id, nix::sys::signal::Signal::SIGTERM).expect("Failed to kill");
+        let kill_output = kill(target_process_id, Signal::SIGTERM).expect("Failed to kill");
         match kill_output {
-            KillOutput::Killed { process_id: _ } => {
+            KillOutput::Killed { process_id: _ } | KillOutput::Failed { .. } => {
                 panic!("This should not happen");
             }
             KillOutput::MaybeAlreadyTerminated { process_id, source } => {
@@ -137,10 +182,9 @@ mod tests {
     #[test]
     fn kii_sigkill() {
         let target_process_id = get_available_max_process_id();
-        let kill_output =
-            kill(target_process_id, nix::sys::signal::Signal::SIGKILL).expect("Failed to kill");
+        let kill_output = kill(target_process_id, Signal::SIGKILL).expect("Failed to kill");
         match kill_output {
-            KillOutput::Killed { process_id: _ } => {
+            KillOutput::Killed { process_id: _ } | KillOutput::Failed { .. } => {
                 panic!("This should not happen");
             }
             KillOutput::MaybeAlreadyTerminated { process_id, source } => {
@@ -153,10 +197,9 @@ mod tests {
     #[test]
     fn kii_sigint() {
         let target_process_id = get_available_max_process_id();
-        let kill_output =
-            kill(target_process_id, nix::sys::signal::Signal::SIGINT).expect("Failed to kill");
+        let kill_output = kill(target_process_id, Signal::SIGINT).expect("Failed to kill");
         match kill_output {
-            KillOutput::Killed { process_id: _ } => {
+            KillOutput::Killed { process_id: _ } | KillOutput::Failed { .. } => {
                 panic!("This should not happen");
             }
             KillOutput::MaybeAlreadyTerminated { process_id, source } => {
@@ -1,9 +1,9 @@
 use crate::core::{
-    Config, Error, KillOutput, Killable, KillableBuildable, ProcessId, ProcessInfo, ProcessInfos,
-    Result,
+    Config, Error, ExitOutcome, KillOutput, Killable, KillableBuildable, ProcessId, ProcessIds,
+    ProcessInfo, ProcessInfos, Result, Signal,
 };
 use std::ffi;
-use tracing::instrument;
+use tracing::{debug, instrument};
 use windows::Win32::{
     Foundation::{CloseHandle, ERROR_NO_MORE_FILES, E_ACCESSDENIED, E_INVALIDARG},
     System::{
@@ -11,7 +11,10 @@ use windows::Win32::{
             CreateToolhelp32Snapshot, Process32First, Process32Next, PROCESSENTRY32,
             TH32CS_SNAPPROCESS,
         },
-        Threading::{OpenProcess, TerminateProcess, PROCESS_TERMINATE},
+        Threading::{
+            GetExitCodeProcess, OpenProcess, QueryFullProcessImageNameW, TerminateProcess,
+            PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_TERMINATE,
+        },
     },
 };
 
@@ -21,6 +24,9 @@ const SYSTEM_IDLE_PROCESS_PROCESS_ID: u32 = 0;
 /// process id of System
 const SYSTEM_PROCESS_ID: u32 = 4;
 
+/// exit code of a process that has not exited yet
+const STILL_ACTIVE_EXIT_CODE: u32 = 259;
+
 /// In hexadecimal, 0xFFFFFFFF.  
 /// In decimal, 4294967295.  
 /// But actually process IDs are generated as multiples of 4.  
@@ -45,6 +51,55 @@ pub(crate) fn child_process_id_map_filter(process_info: &ProcessInfo) -> bool {
     process_info.parent_process_id == process_info.process_id
 }
 
+/// Signals of Linux, to validate `Signal` although it is never sent on Windows.
+const SIGNALS: [(&str, i32); 31] = [
+    ("SIGHUP", 1),
+    ("SIGINT", 2),
+    ("SIGQUIT", 3),
+    ("SIGILL", 4),
+    ("SIGTRAP", 5),
+    ("SIGABRT", 6),
+    ("SIGBUS", 7),
+    ("SIGFPE", 8),
+    ("SIGKILL", 9),
+    ("SIGUSR1", 10),
+    ("SIGSEGV", 11),
+    ("SIGUSR2", 12),
+    ("SIGPIPE", 13),
+    ("SIGALRM", 14),
+    ("SIGTERM", 15),
+    ("SIGSTKFLT", 16),
+    ("SIGCHLD", 17),
+    ("SIGCONT", 18),
+    ("SIGSTOP", 19),
+    ("SIGTSTP", 20),
+    ("SIGTTIN", 21),
+    ("SIGTTOU", 22),
+    ("SIGURG", 23),
+    ("SIGXCPU", 24),
+    ("SIGXFSZ", 25),
+    ("SIGVTALRM", 26),
+    ("SIGPROF", 27),
+    ("SIGWINCH", 28),
+    ("SIGIO", 29),
+    ("SIGPWR", 30),
+    ("SIGSYS", 31),
+];
+
+pub(crate) fn parse_signal_name(name: &str) -> Option<i32> {
+    SIGNALS
+        .iter()
+        .find(|(x, _)| x.strip_prefix("SIG") == Some(name))
+        .map(|(_, number)| *number)
+}
+
+pub(crate) fn get_signal_name(number: i32) -> Option<String> {
+    SIGNALS
+        .iter()
+        .find(|(_, x)| *x == number)
+        .map(|(name, _)| (*name).to_string())
+}
+
 pub(crate) struct Killer {}
 
 impl Killable for Killer {
@@ -55,8 +110,14 @@ impl Killable for Killer {
 
 pub(crate) struct KillerBuilder {}
 
+impl KillerBuilder {
+    pub(crate) fn new(_process_ids: &[ProcessId]) -> Self {
+        Self {}
+    }
+}
+
 impl KillableBuildable for KillerBuilder {
-    fn new_killable(&self, _config: &Config) -> Result<Killer> {
+    fn new_killable(&self, _signal: Signal) -> Result<Killer> {
         Ok(Killer {})
     }
 }
@@ -106,6 +167,128 @@ pub(crate) fn kill(process_id: ProcessId) -> Result<KillOutput> {
     result
 }
 
+/// Returns whether the process has exited or is still alive.  
+#[instrument]
+pub(crate) fn get_exit_outcome(
+    _proc_root: &std::path::Path,
+    process_id: ProcessId,
+) -> Result<ExitOutcome> {
+    let result: Result<ExitOutcome>;
+    unsafe {
+        match OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, process_id) {
+            Ok(process_handle) => {
+                {
+                    // do NOT return early from this block
+                    let mut exit_code = 0_u32;
+                    result = GetExitCodeProcess(process_handle, &mut exit_code)
+                        .map(|()| {
+                            if exit_code == STILL_ACTIVE_EXIT_CODE {
+                                ExitOutcome::StillAlive
+                            } else {
+                                ExitOutcome::Exited
+                            }
+                        })
+                        .map_err(Into::into);
+                }
+                CloseHandle(process_handle)?;
+            }
+            Err(e) => {
+                if e.code() == E_INVALIDARG {
+                    // The parameter is incorrect.
+                    // This happens when the process does not exist.
+                    result = Ok(ExitOutcome::Exited);
+                } else {
+                    result = Err(e.into());
+                }
+            }
+        }
+    }
+    result
+}
+
+/// The process snapshot does not provide the start time, so it is not checked on Windows.  
+#[allow(clippy::unnecessary_wraps)]
+pub(crate) fn get_start_time(
+    _proc_root: &std::path::Path,
+    _process_id: ProcessId,
+) -> Result<Option<u64>> {
+    Ok(None)
+}
+
+/// Reading the command line of another process is not supported on Windows.  
+pub(crate) fn get_cmdline(
+    _proc_root: &std::path::Path,
+    _process_id: ProcessId,
+) -> Option<Vec<String>> {
+    None
+}
+
+/// Pid namespaces are Linux only, so the process id is used as is.
+pub(crate) fn get_caller_process_id(process_id: ProcessId, _config: &Config) -> Result<ProcessId> {
+    Ok(process_id)
+}
+
+/// Procfs is Linux only, so the processes are read as is.
+pub(crate) fn get_read_proc_root(proc_root: &std::path::Path) -> Result<&std::path::Path> {
+    Ok(proc_root)
+}
+
+/// Procfs is Linux only, so the processes are signalled as is.
+pub(crate) fn get_signal_proc_root(proc_root: &std::path::Path) -> Result<&std::path::Path> {
+    Ok(proc_root)
+}
+
+/// Pid namespaces are Linux only.
+pub(crate) fn get_namespace_process_ids(
+    _proc_root: &std::path::Path,
+    _process_id: ProcessId,
+) -> (Option<ProcessIds>, Option<ProcessIds>) {
+    (None, None)
+}
+
+/// Returns the path of the executable of the process.  
+/// `None` if there is no such process, or it is not allowed to inquire.  
+pub(crate) fn get_exe(
+    _proc_root: &std::path::Path,
+    process_id: ProcessId,
+) -> Option<std::path::PathBuf> {
+    use std::os::windows::ffi::OsStringExt;
+
+    let mut buffer = [0_u16; 32768];
+    let mut size = u32::try_from(buffer.len()).ok()?;
+    let result: windows::core::Result<()>;
+    unsafe {
+        let process_handle = match OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, process_id)
+        {
+            Ok(x) => x,
+            Err(e) => {
+                debug!(process_id, error = ?e, "Failed to open process");
+                return None;
+            }
+        };
+        {
+            // do NOT return early from this block
+            result = QueryFullProcessImageNameW(
+                process_handle,
+                PROCESS_NAME_WIN32,
+                windows::core::PWSTR(buffer.as_mut_ptr()),
+                &mut size,
+            );
+        }
+        if let Err(e) = CloseHandle(process_handle) {
+            debug!(process_id, error = ?e, "Failed to close process handle");
+        }
+    }
+    if let Err(e) = result {
+        debug!(process_id, error = ?e, "Failed to query process image name");
+        return None;
+    }
+    let size = usize::try_from(size).ok()?;
+    Some(std::path::PathBuf::from(std::ffi::OsString::from_wide(
+        buffer.get(..size)?,
+    )))
+}
+
 #[instrument]
 pub(crate) fn get_process_infos() -> Result<ProcessInfos> {
     let mut process_infos = ProcessInfos::new();
@@ -127,6 +310,16 @@ pub(crate) fn get_process_infos() -> Result<ProcessInfos> {
                                 name: ffi::CStr::from_ptr(process_entry.szExeFile.as_ptr().cast())
                                     .to_string_lossy()
                                     .into_owned(),
+                                process_group_id: None,
+                                session_id: None,
+                                start_time: None,
+                                user_id: None,
+                                group_id: None,
+                                state: None,
+                                cmdline: None,
+                                exe: None,
+                                namespace_process_ids: None,
+                                namespace_process_group_ids: None,
                             });
                             match Process32Next(snapshot_handle, &mut process_entry) {
                                 Ok(()) => {}
@@ -163,10 +356,22 @@ pub(crate) fn get_process_infos() -> Result<ProcessInfos> {
 #[cfg(feature = "blocking")]
 pub(crate) mod blocking {
     use super::{ProcessInfos, Result};
-    use crate::core::blocking::ProcessInfosProvidable;
+    use crate::core::{blocking::ProcessInfosProvidable, Config, ProcessId};
 
     pub(crate) struct ProcessInfosProvider {}
 
+    pub(crate) fn new_process_infos_provider(_config: &Config) -> ProcessInfosProvider {
+        ProcessInfosProvider {}
+    }
+
+    /// Provides every process, since the children of a process cannot be listed directly.
+    pub(crate) fn new_tree_process_infos_provider(
+        _process_ids: &[ProcessId],
+        _config: &Config,
+    ) -> ProcessInfosProvider {
+        ProcessInfosProvider {}
+    }
+
     impl ProcessInfosProvidable for ProcessInfosProvider {
         fn get_process_infos(&self) -> Result<ProcessInfos> {
             crate::windows::get_process_infos()
@@ -177,10 +382,22 @@ pub(crate) mod blocking {
 #[cfg(feature = "tokio")]
 pub(crate) mod tokio {
     use super::{ProcessInfos, Result};
-    use crate::core::tokio::ProcessInfosProvidable;
+    use crate::core::{tokio::ProcessInfosProvidable, Config, ProcessId};
 
     pub(crate) struct ProcessInfosProvider {}
 
+    pub(crate) fn new_process_infos_provider(_config: &Config) -> ProcessInfosProvider {
+        ProcessInfosProvider {}
+    }
+
+    /// Provides every process, since the children of a process cannot be listed directly.
+    pub(crate) fn new_tree_process_infos_provider(
+        _process_ids: &[ProcessId],
+        _config: &Config,
+    ) -> ProcessInfosProvider {
+        ProcessInfosProvider {}
+    }
+
     impl ProcessInfosProvidable for ProcessInfosProvider {
         async fn get_process_infos(&self) -> Result<ProcessInfos> {
             crate::windows::get_process_infos()
@@ -240,6 +457,7 @@ mod tests {
             process_id: 1,
             parent_process_id: 1,
             name: "1".to_string(),
+            ..Default::default()
         };
         assert!(child_process_id_map_filter(&process_info));
     }
@@ -250,6 +468,7 @@ mod tests {
             process_id: 1,
             parent_process_id: 0,
             name: "1".to_string(),
+            ..Default::default()
         };
         assert!(!child_process_id_map_filter(&process_info));
     }
@@ -272,9 +491,35 @@ mod tests {
                 }
             }
             KillOutput::Killed { process_id } => panic!("Unexpected result: {process_id}"),
+            KillOutput::Failed { process_id, .. } => panic!("Unexpected failure: {process_id}"),
         }
     }
 
+    #[test]
+    fn get_exe_self() {
+        let exe =
+            get_exe(std::path::Path::new("/proc"), std::process::id()).expect("Failed to get exe");
+        assert_eq!(
+            exe,
+            std::env::current_exe().expect("Failed to get current exe")
+        );
+    }
+
+    #[test]
+    fn get_exit_outcome_self() {
+        let exit_outcome = get_exit_outcome(std::path::Path::new("/proc"), std::process::id())
+            .expect("Failed to get exit outcome");
+        assert_eq!(exit_outcome, ExitOutcome::StillAlive);
+    }
+
+    #[test]
+    fn get_exit_outcome_available_max_process_id() {
+        let exit_outcome =
+            get_exit_outcome(std::path::Path::new("/proc"), AVAILABLE_MAX_PROCESS_ID)
+                .expect("Failed to get exit outcome");
+        assert_eq!(exit_outcome, ExitOutcome::Exited);
+    }
+
     #[test]
     fn get_process_infos_test() {
         let result = get_process_infos();
@@ -0,0 +1,68 @@
+#![cfg(all(target_os = "linux", feature = "blocking"))]
+
+use std::{process::Command, thread, time::Duration};
+use tracing_test::traced_test;
+
+fn get_node_script_spawn_orphan() -> String {
+    r"
+    const { spawn } = require('child_process');
+    // This process exits after spawning the child, so the child is orphaned.
+    spawn('node', ['-e', 'setInterval(() => {}, 1000);'], { stdio: 'inherit' });
+    setTimeout(() => process.exit(0), 100);
+    "
+    .to_string()
+}
+
+/// This is the only test in this binary,
+/// because it kills and reaps every child of the current process.
+#[traced_test]
+#[test]
+fn kill_tree_orphan_with_child_subreaper() {
+    kill_tree::set_child_subreaper().expect("Failed to set child subreaper");
+    let mut child = Command::new("node")
+        .arg("-e")
+        .arg(get_node_script_spawn_orphan())
+        .spawn()
+        .unwrap();
+    let child_process_id = child.id();
+    child.wait().unwrap();
+    thread::sleep(Duration::from_secs(1));
+    let config = kill_tree::Config {
+        include_target: false,
+        ..Default::default()
+    };
+    let outputs = kill_tree::blocking::kill_tree_with_config(std::process::id(), &config)
+        .expect("Failed to kill");
+    println!("{outputs:?}");
+    assert_eq!(outputs.len(), 1);
+    let orphan_process_id = match &outputs[0] {
+        kill_tree::Output::Killed {
+            process_id,
+            parent_process_id,
+            ..
+        } => {
+            assert_eq!(*parent_process_id, std::process::id());
+            *process_id
+        }
+        output => panic!("Unexpected output: {output:?}"),
+    };
+    assert_ne!(orphan_process_id, child_process_id);
+    // A direct child still held as `Child` must be left for `Child::wait`.
+    let mut held_child = Command::new("sleep").arg("0").spawn().unwrap();
+    let held_child_process_id = held_child.id();
+    let mut reaped_process_ids = Vec::new();
+    for _ in 0..50 {
+        reaped_process_ids
+            .extend(kill_tree::reap_zombies(&[held_child_process_id]).expect("Failed to reap"));
+        if reaped_process_ids.contains(&orphan_process_id) {
+            break;
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    assert_eq!(reaped_process_ids, vec![orphan_process_id]);
+    thread::sleep(Duration::from_millis(100));
+    assert!(kill_tree::reap_zombies(&[held_child_process_id])
+        .expect("Failed to reap")
+        .is_empty());
+    assert!(held_child.wait().unwrap().success());
+}
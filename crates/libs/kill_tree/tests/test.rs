@@ -26,6 +26,41 @@ fn get_node_script_spawn_infinite_child() -> String {
     .to_string()
 }
 
+fn get_node_script_ignore_sigterm() -> String {
+    r"
+    process.on('SIGTERM', () => {});
+    setInterval(() => {}, 1000);
+    "
+    .to_string()
+}
+
+fn get_node_script_spawn_child_repeatedly() -> String {
+    r"
+    const { spawn } = require('child_process');
+    setInterval(() => {
+        spawn('node', ['-e', 'setInterval(() => {}, 1000);'], {
+            stdio: 'inherit',
+        });
+    }, 100);
+    "
+    .to_string()
+}
+
+fn get_node_script_spawn_daemon() -> String {
+    r"
+    const { spawn } = require('child_process');
+    // The child exits after spawning the grandchild, so the grandchild is reparented.
+    const script = `
+        const { spawn } = require('child_process');
+        spawn('node', ['-e', 'setInterval(() => {}, 1000);'], { stdio: 'inherit' });
+        setTimeout(() => process.exit(0), 100);
+    `;
+    spawn('node', ['-e', script], { stdio: 'inherit' });
+    setInterval(() => {}, 1000);
+    "
+    .to_string()
+}
+
 #[traced_test]
 #[test]
 fn kill_tree_default() {
@@ -51,6 +86,7 @@ fn kill_tree_default() {
             process_id,
             parent_process_id,
             name,
+            ..
         } => {
             assert_eq!(*process_id, target_process_id);
             assert_eq!(*parent_process_id, std::process::id());
@@ -58,7 +94,9 @@ fn kill_tree_default() {
             println!("name: {name}");
             assert!(name.starts_with("node"));
         }
-        kill_tree::Output::MaybeAlreadyTerminated { .. } => {
+        kill_tree::Output::MaybeAlreadyTerminated { .. }
+        | kill_tree::Output::PidReused { .. }
+        | kill_tree::Output::Failed { .. } => {
             panic!("This should not happen");
         }
     }
@@ -81,7 +119,7 @@ fn kill_tree_with_config_sigkill() {
     });
     let target_process_id = rx.recv().unwrap();
     let config = kill_tree::Config {
-        signal: String::from("SIGKILL"),
+        signal: kill_tree::Signal::SIGKILL,
         ..Default::default()
     };
     let outputs = kill_tree::blocking::kill_tree_with_config(target_process_id, &config)
@@ -94,12 +132,15 @@ fn kill_tree_with_config_sigkill() {
             process_id,
             parent_process_id,
             name,
+            ..
         } => {
             assert_eq!(*process_id, target_process_id);
             assert_eq!(*parent_process_id, std::process::id());
             assert!(name.starts_with("node"));
         }
-        kill_tree::Output::MaybeAlreadyTerminated { .. } => {
+        kill_tree::Output::MaybeAlreadyTerminated { .. }
+        | kill_tree::Output::PidReused { .. }
+        | kill_tree::Output::Failed { .. } => {
             panic!("This should not happen");
         }
     }
@@ -136,11 +177,14 @@ fn kill_tree_with_config_include_target_false() {
             process_id: _,
             parent_process_id,
             name,
+            ..
         } => {
             assert_eq!(*parent_process_id, target_process_id);
             assert!(name.starts_with("node"));
         }
-        kill_tree::Output::MaybeAlreadyTerminated { .. } => {
+        kill_tree::Output::MaybeAlreadyTerminated { .. }
+        | kill_tree::Output::PidReused { .. }
+        | kill_tree::Output::Failed { .. } => {
             panic!("This should not happen");
         }
     }
@@ -154,12 +198,15 @@ fn kill_tree_with_config_include_target_false() {
             process_id,
             parent_process_id,
             name,
+            ..
         } => {
             assert_eq!(*process_id, target_process_id);
             assert_eq!(*parent_process_id, std::process::id());
             assert!(name.starts_with("node"));
         }
-        kill_tree::Output::MaybeAlreadyTerminated { .. } => {
+        kill_tree::Output::MaybeAlreadyTerminated { .. }
+        | kill_tree::Output::PidReused { .. }
+        | kill_tree::Output::Failed { .. } => {
             panic!("This should not happen");
         }
     }
@@ -190,8 +237,11 @@ fn kill_tree_child_tree() {
                 process_id: _,
                 parent_process_id,
                 name: _,
+                ..
             } => *parent_process_id == std::process::id(),
-            kill_tree::Output::MaybeAlreadyTerminated { .. } => false,
+            kill_tree::Output::MaybeAlreadyTerminated { .. }
+            | kill_tree::Output::PidReused { .. }
+            | kill_tree::Output::Failed { .. } => false,
         })
         .unwrap();
     match target_output {
@@ -199,12 +249,15 @@ fn kill_tree_child_tree() {
             process_id,
             parent_process_id,
             name,
+            ..
         } => {
             assert_eq!(*process_id, target_process_id);
             assert_eq!(*parent_process_id, std::process::id());
             assert!(name.starts_with("node"));
         }
-        kill_tree::Output::MaybeAlreadyTerminated { .. } => {
+        kill_tree::Output::MaybeAlreadyTerminated { .. }
+        | kill_tree::Output::PidReused { .. }
+        | kill_tree::Output::Failed { .. } => {
             panic!("This should not happen");
         }
     }
@@ -215,8 +268,11 @@ fn kill_tree_child_tree() {
                 process_id: _,
                 parent_process_id,
                 name: _,
+                ..
             } => *parent_process_id == target_process_id,
-            kill_tree::Output::MaybeAlreadyTerminated { .. } => false,
+            kill_tree::Output::MaybeAlreadyTerminated { .. }
+            | kill_tree::Output::PidReused { .. }
+            | kill_tree::Output::Failed { .. } => false,
         })
         .unwrap();
     match child_output {
@@ -224,13 +280,517 @@ fn kill_tree_child_tree() {
             process_id: _,
             parent_process_id,
             name,
+            ..
         } => {
             assert_eq!(*parent_process_id, target_process_id);
             assert!(name.starts_with("node"));
         }
-        kill_tree::Output::MaybeAlreadyTerminated { .. } => {
+        kill_tree::Output::MaybeAlreadyTerminated { .. }
+        | kill_tree::Output::PidReused { .. }
+        | kill_tree::Output::Failed { .. } => {
+            panic!("This should not happen");
+        }
+    }
+    thread.join().unwrap();
+}
+
+#[test]
+fn plan_kill_tree_child_tree() {
+    let (tx, rx) = mpsc::channel();
+    let thread = thread::spawn(move || {
+        let mut child = Command::new("node")
+            .arg("-e")
+            .arg(get_node_script_spawn_infinite_child())
+            .spawn()
+            .unwrap();
+        thread::sleep(Duration::from_secs(1));
+        let target_process_id = child.id();
+        tx.send(target_process_id).unwrap();
+        let _ = child.wait();
+    });
+    let target_process_id = rx.recv().unwrap();
+    let kill_plan =
+        kill_tree::blocking::plan_kill_tree(target_process_id, &kill_tree::Config::default())
+            .expect("Failed to plan");
+    assert_eq!(kill_plan.len(), 2);
+    let child_entry = &kill_plan[0];
+    assert_eq!(child_entry.parent_process_id, Some(target_process_id));
+    assert_eq!(child_entry.depth, 1);
+    assert_eq!(child_entry.reason, kill_tree::KillReason::Descendant);
+    let target_entry = &kill_plan[1];
+    assert_eq!(target_entry.process_id, target_process_id);
+    assert_eq!(target_entry.parent_process_id, Some(std::process::id()));
+    assert_eq!(target_entry.depth, 0);
+    assert_eq!(target_entry.reason, kill_tree::KillReason::Target);
+    // Nothing is killed by planning.
+    let outputs = kill_tree::blocking::kill_tree(target_process_id).expect("Failed to kill");
+    assert_eq!(outputs.len(), 2);
+    thread.join().unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn kill_tree_with_config_escalation() {
+    let (tx, rx) = mpsc::channel();
+    let thread = thread::spawn(move || {
+        let mut child = Command::new("node")
+            .arg("-e")
+            .arg(get_node_script_ignore_sigterm())
+            .spawn()
+            .unwrap();
+        let target_process_id = child.id();
+        thread::sleep(Duration::from_secs(1));
+        tx.send(target_process_id).unwrap();
+        let _ = child.wait();
+    });
+    let target_process_id = rx.recv().unwrap();
+    let config = kill_tree::Config {
+        escalation: vec![
+            kill_tree::EscalationStep {
+                signal: kill_tree::Signal::SIGTERM,
+                timeout: Duration::from_millis(500),
+            },
+            kill_tree::EscalationStep {
+                signal: kill_tree::Signal::SIGKILL,
+                timeout: Duration::from_secs(5),
+            },
+        ],
+        ..Default::default()
+    };
+    let outputs = kill_tree::blocking::kill_tree_with_config(target_process_id, &config)
+        .expect("Failed to kill");
+    println!("{outputs:?}");
+    assert_eq!(outputs.len(), 1);
+    let output = &outputs[0];
+    match output {
+        kill_tree::Output::Killed {
+            process_id,
+            ended_by_step,
+            ..
+        } => {
+            assert_eq!(*process_id, target_process_id);
+            // SIGTERM is ignored, so the process is ended by SIGKILL.
+            assert_eq!(*ended_by_step, Some(1));
+        }
+        kill_tree::Output::MaybeAlreadyTerminated { .. }
+        | kill_tree::Output::PidReused { .. }
+        | kill_tree::Output::Failed { .. } => {
             panic!("This should not happen");
         }
     }
     thread.join().unwrap();
 }
+
+#[test]
+fn kill_tree_with_config_wait_for_exit() {
+    let (tx, rx) = mpsc::channel();
+    let thread = thread::spawn(move || {
+        let mut child = Command::new("node")
+            .arg("-e")
+            .arg(get_node_script_spawn_infinite_child())
+            .spawn()
+            .unwrap();
+        thread::sleep(Duration::from_secs(1));
+        let target_process_id = child.id();
+        tx.send(target_process_id).unwrap();
+        let _ = child.wait();
+    });
+    let target_process_id = rx.recv().unwrap();
+    let config = kill_tree::Config {
+        wait_for_exit: Some(Duration::from_secs(5)),
+        ..Default::default()
+    };
+    let outputs = kill_tree::blocking::kill_tree_with_config(target_process_id, &config)
+        .expect("Failed to kill");
+    println!("{outputs:?}");
+    assert_eq!(outputs.len(), 2);
+    for output in &outputs {
+        match output {
+            kill_tree::Output::Killed { exit_outcome, .. } => {
+                // The process may not be reaped by its parent yet.
+                assert!(matches!(
+                    exit_outcome,
+                    Some(kill_tree::ExitOutcome::Exited | kill_tree::ExitOutcome::Zombie)
+                ));
+            }
+            kill_tree::Output::MaybeAlreadyTerminated { .. }
+            | kill_tree::Output::PidReused { .. }
+            | kill_tree::Output::Failed { .. } => {
+                panic!("This should not happen");
+            }
+        }
+    }
+    thread.join().unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn kill_tree_with_config_freeze() {
+    let (tx, rx) = mpsc::channel();
+    let thread = thread::spawn(move || {
+        let mut child = Command::new("node")
+            .arg("-e")
+            .arg(get_node_script_spawn_child_repeatedly())
+            .spawn()
+            .unwrap();
+        thread::sleep(Duration::from_secs(1));
+        let target_process_id = child.id();
+        tx.send(target_process_id).unwrap();
+        let _ = child.wait();
+    });
+    let target_process_id = rx.recv().unwrap();
+    let config = kill_tree::Config {
+        freeze: true,
+        wait_for_exit: Some(Duration::from_secs(5)),
+        ..Default::default()
+    };
+    let outputs = kill_tree::blocking::kill_tree_with_config(target_process_id, &config)
+        .expect("Failed to kill");
+    println!("{outputs:?}");
+    assert!(outputs.len() > 1);
+    for output in &outputs {
+        match output {
+            kill_tree::Output::Killed { exit_outcome, .. } => {
+                // The process may not be reaped by its parent yet.
+                assert!(matches!(
+                    exit_outcome,
+                    Some(kill_tree::ExitOutcome::Exited | kill_tree::ExitOutcome::Zombie)
+                ));
+            }
+            kill_tree::Output::MaybeAlreadyTerminated { .. }
+            | kill_tree::Output::PidReused { .. }
+            | kill_tree::Output::Failed { .. } => {
+                panic!("This should not happen");
+            }
+        }
+    }
+    thread.join().unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn kill_tree_with_config_repeat() {
+    let (tx, rx) = mpsc::channel();
+    let thread = thread::spawn(move || {
+        let mut child = Command::new("node")
+            .arg("-e")
+            .arg(get_node_script_ignore_sigterm())
+            .spawn()
+            .unwrap();
+        thread::sleep(Duration::from_secs(1));
+        let target_process_id = child.id();
+        tx.send(target_process_id).unwrap();
+        let _ = child.wait();
+    });
+    let target_process_id = rx.recv().unwrap();
+    let config = kill_tree::Config {
+        repeat: Some(kill_tree::RepeatLimit {
+            max_rounds: 3,
+            deadline: None,
+        }),
+        ..Default::default()
+    };
+    let outputs = kill_tree::blocking::kill_tree_with_config(target_process_id, &config)
+        .expect("Failed to kill");
+    println!("{outputs:?}");
+    // SIGTERM is ignored, so the process is found again in every round.
+    assert_eq!(outputs.len(), 3);
+    for (index, output) in outputs.iter().enumerate() {
+        match output {
+            kill_tree::Output::Killed {
+                process_id, round, ..
+            } => {
+                assert_eq!(*process_id, target_process_id);
+                assert_eq!(*round, index);
+            }
+            kill_tree::Output::MaybeAlreadyTerminated { .. }
+            | kill_tree::Output::PidReused { .. }
+            | kill_tree::Output::Failed { .. } => {
+                panic!("This should not happen");
+            }
+        }
+    }
+    let config = kill_tree::Config {
+        signal: kill_tree::Signal::SIGKILL,
+        ..Default::default()
+    };
+    kill_tree::blocking::kill_tree_with_config(target_process_id, &config).expect("Failed to kill");
+    thread.join().unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn kill_tree_with_config_selection_process_group() {
+    use std::os::unix::process::CommandExt;
+
+    let (tx, rx) = mpsc::channel();
+    let thread = thread::spawn(move || {
+        // Not to be in the same process group as the test process.
+        let mut child = Command::new("node")
+            .arg("-e")
+            .arg(get_node_script_spawn_daemon())
+            .process_group(0)
+            .spawn()
+            .unwrap();
+        thread::sleep(Duration::from_secs(1));
+        let target_process_id = child.id();
+        tx.send(target_process_id).unwrap();
+        let _ = child.wait();
+    });
+    let target_process_id = rx.recv().unwrap();
+    let config = kill_tree::Config {
+        selection: kill_tree::Selection::ProcessGroup,
+        ..Default::default()
+    };
+    let kill_plan =
+        kill_tree::blocking::plan_kill_tree(target_process_id, &config).expect("Failed to plan");
+    println!("{kill_plan:?}");
+    assert_eq!(kill_plan.len(), 2);
+    assert_eq!(kill_plan[0].reason, kill_tree::KillReason::ProcessGroup);
+    assert_eq!(kill_plan[1].process_id, target_process_id);
+    let outputs = kill_tree::blocking::kill_tree_with_config(target_process_id, &config)
+        .expect("Failed to kill");
+    println!("{outputs:?}");
+    assert_eq!(outputs.len(), 2);
+    for output in &outputs {
+        assert!(matches!(output, kill_tree::Output::Killed { .. }));
+    }
+    thread.join().unwrap();
+}
+
+#[traced_test]
+#[test]
+fn kill_tree_with_config_filter() {
+    let (tx, rx) = mpsc::channel();
+    let thread = thread::spawn(move || {
+        let mut child = Command::new("node")
+            .arg("-e")
+            .arg(get_node_script_spawn_infinite_child())
+            .spawn()
+            .unwrap();
+        let target_process_id = child.id();
+        thread::sleep(Duration::from_secs(1));
+        tx.send(target_process_id).unwrap();
+        let _ = child.wait();
+    });
+    let target_process_id = rx.recv().unwrap();
+    let kill_plan =
+        kill_tree::blocking::plan_kill_tree(target_process_id, &kill_tree::Config::default())
+            .expect("Failed to plan");
+    assert_eq!(kill_plan.len(), 2);
+    let sidecar_process_id = kill_plan[0].process_id;
+    // keep the child process alive like a sidecar
+    let config = kill_tree::Config {
+        filter: Some(Box::new(|x| {
+            #[cfg(unix)]
+            assert!(x.cmdline().is_some_and(|x| x[0] == "node"));
+            if x.depth() == 1 {
+                kill_tree::FilterDecision::Skip
+            } else {
+                kill_tree::FilterDecision::Include
+            }
+        })),
+        ..Default::default()
+    };
+    let outputs = kill_tree::blocking::kill_tree_with_config(target_process_id, &config)
+        .expect("Failed to kill");
+    println!("{outputs:?}");
+    assert_eq!(outputs.len(), 1);
+    assert!(matches!(
+        outputs[0],
+        kill_tree::Output::Killed { process_id, .. } if process_id == target_process_id
+    ));
+    thread.join().unwrap();
+    let outputs = kill_tree::blocking::kill_tree(sidecar_process_id).expect("Failed to kill");
+    println!("{outputs:?}");
+    assert!(matches!(outputs[0], kill_tree::Output::Killed { .. }));
+}
+
+#[traced_test]
+#[test]
+fn kill_trees_siblings() {
+    let (tx, rx) = mpsc::channel();
+    let threads = (0..2)
+        .map(|_| {
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let mut child = Command::new("node")
+                    .arg("-e")
+                    .arg(get_node_script_spawn_infinite_child())
+                    .spawn()
+                    .unwrap();
+                let target_process_id = child.id();
+                thread::sleep(Duration::from_secs(1));
+                tx.send(target_process_id).unwrap();
+                let _ = child.wait();
+            })
+        })
+        .collect::<Vec<_>>();
+    let target_process_ids = [rx.recv().unwrap(), rx.recv().unwrap()];
+    let tree_outputs =
+        kill_tree::blocking::kill_trees(&target_process_ids, &kill_tree::Config::default())
+            .expect("Failed to kill");
+    println!("{tree_outputs:?}");
+    assert_eq!(tree_outputs.len(), 2);
+    for (tree_output, target_process_id) in tree_outputs.iter().zip(target_process_ids) {
+        assert_eq!(tree_output.process_id, target_process_id);
+        assert_eq!(tree_output.outputs.len(), 2);
+        for output in &tree_output.outputs {
+            assert!(matches!(output, kill_tree::Output::Killed { .. }));
+        }
+    }
+    for thread in threads {
+        thread.join().unwrap();
+    }
+}
+
+#[cfg(feature = "blocking")]
+#[test]
+fn kill_trees_by_selector_cmdline() {
+    let marker = format!("kill-tree-selector-{}", std::process::id());
+    let (tx, rx) = mpsc::channel();
+    let thread = {
+        let marker = marker.clone();
+        thread::spawn(move || {
+            let mut child = Command::new("node")
+                .arg("-e")
+                .arg(get_node_script_spawn_infinite_child())
+                .arg(marker)
+                .spawn()
+                .unwrap();
+            let target_process_id = child.id();
+            thread::sleep(Duration::from_secs(1));
+            tx.send(target_process_id).unwrap();
+            let _ = child.wait();
+        })
+    };
+    let target_process_id = rx.recv().unwrap();
+    let selector = kill_tree::ProcessSelector::new().cmdline(move |x| x.contains(&marker));
+    let selected_process_ids =
+        kill_tree::blocking::select_process_ids(&selector).expect("Failed to select");
+    assert_eq!(selected_process_ids, vec![target_process_id]);
+    let tree_outputs =
+        kill_tree::blocking::kill_trees_by_selector(&selector, &kill_tree::Config::default())
+            .expect("Failed to kill");
+    println!("{tree_outputs:?}");
+    assert_eq!(tree_outputs.len(), 1);
+    assert_eq!(tree_outputs[0].process_id, target_process_id);
+    assert_eq!(tree_outputs[0].outputs.len(), 2);
+    thread.join().unwrap();
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn wait_tree_exit_child_tree() {
+    let (tx, rx) = mpsc::channel();
+    let thread = thread::spawn(move || {
+        let mut child = Command::new("node")
+            .arg("-e")
+            .arg(get_node_script_spawn_infinite_child())
+            .spawn()
+            .unwrap();
+        let target_process_id = child.id();
+        thread::sleep(Duration::from_secs(1));
+        tx.send(target_process_id).unwrap();
+        let _ = child.wait();
+    });
+    let target_process_id = rx.recv().unwrap();
+    let outputs = kill_tree::tokio::kill_tree(target_process_id)
+        .await
+        .expect("Failed to kill");
+    let process_exits = kill_tree::tokio::wait_tree_exit(&outputs, Duration::from_secs(5))
+        .await
+        .expect("Failed to wait");
+    println!("{process_exits:?}");
+    assert_eq!(process_exits.len(), 2);
+    for process_exit in &process_exits {
+        assert_ne!(
+            process_exit.exit_outcome,
+            kill_tree::ExitOutcome::StillAlive
+        );
+    }
+    thread.join().unwrap();
+}
+
+#[cfg(feature = "blocking")]
+#[test]
+fn kill_tree_with_provider_static_process_table() {
+    let (tx, rx) = mpsc::channel();
+    let thread = thread::spawn(move || {
+        let mut child = Command::new("node")
+            .arg("-e")
+            .arg(get_node_script_infinite())
+            .spawn()
+            .unwrap();
+        let target_process_id = child.id();
+        tx.send(target_process_id).unwrap();
+        let _ = child.wait();
+    });
+    let target_process_id = rx.recv().unwrap();
+    // The child is only in the table, so it has never existed.
+    let child_process_id = kill_tree::get_available_max_process_id();
+    let process_table = kill_tree::StaticProcessTable::default()
+        .process(kill_tree::ProcessInfo::new(
+            target_process_id,
+            std::process::id(),
+            "node",
+        ))
+        .process(kill_tree::ProcessInfo::new(
+            child_process_id,
+            target_process_id,
+            "worker",
+        ));
+    let config = kill_tree::Config::default();
+    let kill_plan = kill_tree::blocking::plan_kill_tree_with_provider(
+        target_process_id,
+        &config,
+        &process_table,
+    )
+    .expect("Failed to plan");
+    assert_eq!(kill_plan.len(), 2);
+    assert_eq!(kill_plan[0].process_id, child_process_id);
+    let outputs =
+        kill_tree::blocking::kill_tree_with_provider(target_process_id, &config, &process_table)
+            .expect("Failed to kill");
+    println!("{outputs:?}");
+    assert_eq!(outputs.len(), 2);
+    for output in &outputs {
+        match output {
+            kill_tree::Output::Killed { process_id, .. } => {
+                assert_eq!(*process_id, target_process_id);
+            }
+            kill_tree::Output::MaybeAlreadyTerminated { process_id, .. } => {
+                assert_eq!(*process_id, child_process_id);
+            }
+            kill_tree::Output::PidReused { .. } | kill_tree::Output::Failed { .. } => {
+                panic!("Unexpected output: {output:?}")
+            }
+        }
+    }
+    thread.join().unwrap();
+}
+
+#[cfg(all(target_os = "linux", feature = "blocking"))]
+#[test]
+fn plan_kill_tree_proc_root_fixture() {
+    let config = kill_tree::Config {
+        proc_root: std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/proc"),
+        ..Default::default()
+    };
+    let kill_plan = kill_tree::blocking::plan_kill_tree(100, &config).expect("Failed to plan");
+    let process_ids = kill_plan.iter().map(|x| x.process_id).collect::<Vec<_>>();
+    assert_eq!(process_ids, vec![101, 100]);
+    assert_eq!(kill_plan[1].name.as_deref(), Some("Web Content"));
+}
+
+#[cfg(all(target_os = "linux", feature = "blocking"))]
+#[test]
+fn kill_tree_proc_root_fixture_refused() {
+    let config = kill_tree::Config {
+        proc_root: std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/proc"),
+        signal: kill_tree::Signal::SIGCONT,
+        ..Default::default()
+    };
+    let result = kill_tree::blocking::kill_tree_with_config(100, &config);
+    assert!(matches!(result, Err(kill_tree::Error::Io(_))));
+}